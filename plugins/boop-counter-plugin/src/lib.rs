@@ -19,8 +19,10 @@ static mut RUNNING: bool = false;
 static mut LAST_BOOP_STATE: bool = false;
 static mut TODAY_BOOPS: u32 = 0;
 static mut TOTAL_BOOPS: u32 = 0;
-static mut LAST_CHATBOX_SEND: u32 = 0;
-static mut TICK_COUNT: u32 = 0;
+// Milliseconds since plugin_start, summed from plugin_update's dt_ms
+static mut ELAPSED_MS: u64 = 0;
+static mut LAST_CHATBOX_SEND: u64 = 0;
+static mut LAST_DAY_CHECK: u64 = 0;
 static mut PENDING_BOOP: bool = false;
 
 // Last boop timestamp (Unix timestamp - seconds since epoch)
@@ -207,9 +209,15 @@ fn str_to_u64(s: &str) -> u64 {
     result
 }
 
+// Host ABI version this plugin was built against
+#[no_mangle]
+pub extern "C" fn plugin_abi_version() -> u32 {
+    1
+}
+
 #[no_mangle]
 pub extern "C" fn plugin_info() -> *const u8 {
-    let json = r#"{"name":"Boop Counter","version":"0.1.0","description":"Counts boops and sends chatbox messages"}"#;
+    let json = r#"{"name":"Boop Counter","version":"0.1.0","description":"Counts boops and sends chatbox messages","capabilities":["listens_osc","sends_chatbox"]}"#;
     write_string(json)
 }
 
@@ -222,7 +230,7 @@ pub extern "C" fn plugin_ui_config() -> *const u8 {
         // Simple UI: just the config and buttons, NO counter labels
         let json = r#"{"title":"Boop Counter","elements":[{"Label":{"text":"OSC Configuration"}},{"TextInput":{"id":"boop_input","label":"Boop Input:","default_value":""#;
         
-        let mut buffer = [0u8; 512];
+        let mut buffer = [0u8; 768];
         let mut pos = 0;
         
         buffer[pos..pos + json.len()].copy_from_slice(json.as_bytes());
@@ -233,7 +241,7 @@ pub extern "C" fn plugin_ui_config() -> *const u8 {
         pos += current_addr.len();
         
         // Buttons
-        let end = br#"","placeholder":"OSC address"}},{"Separator":null},{"Button":{"id":"send_msg","label":"Send Boop Message"}},{"Button":{"id":"reset_today","label":"Reset Today Boops (undo test boops)"}}]}"#;
+        let end = br#"","placeholder":"OSC address","osc_address":true}},{"Separator":null},{"Button":{"id":"send_msg","label":"Send Boop Message"}},{"Button":{"id":"reset_today","label":"Reset Today Boops (undo test boops)","confirm":"This removes today's boops from the total as well. This can't be undone."}}]}"#;
         buffer[pos..pos + end.len()].copy_from_slice(end);
         pos += end.len();
         
@@ -245,7 +253,8 @@ pub extern "C" fn plugin_ui_config() -> *const u8 {
 #[no_mangle]
 pub extern "C" fn plugin_load_config() {
     // Load address
-    if let Some(addr) = load_config_value("boop_input_address") {
+    // The host saves the input under its id; older versions used "boop_input_address"
+    if let Some(addr) = load_config_value("boop_input").or_else(|| load_config_value("boop_input_address")) {
         unsafe {
             let len = addr.len().min(127);
             BOOP_INPUT_ADDR[..len].copy_from_slice(&addr.as_bytes()[..len]);
@@ -364,12 +373,9 @@ pub extern "C" fn plugin_ui_event(event_ptr: i32, event_len: i32) {
                     BOOP_INPUT_ADDR[..len].copy_from_slice(&addr.as_bytes()[..len]);
                     BOOP_INPUT_LEN = len;
                     
-                    // Save to config file
-                    save_config_value("boop_input_address", addr);
-                    
                     // Log it
                     let mut msg = [0u8; 256];
-                    let prefix = b"Saved new address: ";
+                    let prefix = b"Using new address: ";
                     msg[..prefix.len()].copy_from_slice(prefix);
                     msg[prefix.len()..prefix.len() + addr.len()].copy_from_slice(addr.as_bytes());
                     let full_msg = core::str::from_utf8_unchecked(&msg[..prefix.len() + addr.len()]);
@@ -396,13 +402,42 @@ pub extern "C" fn plugin_on_osc_bool(value: i32) {
     }
 }
 
+// Status line for the Plugins tab. Polled every few seconds, so it reuses one
+// static buffer (length prefix + text) instead of the bump allocator.
+static mut STATUS_BUF: [u8; 128] = [0; 128];
+
+#[no_mangle]
+pub extern "C" fn plugin_status() -> *const u8 {
+    unsafe {
+        let mut pos = 4;
+        
+        let prefix: &[u8] = if RUNNING { b"Running, " } else { b"Stopped, " };
+        STATUS_BUF[pos..pos + prefix.len()].copy_from_slice(prefix);
+        pos += prefix.len();
+        
+        let mut num_buf = [0u8; 10];
+        let today_str = u32_to_str(TODAY_BOOPS, &mut num_buf);
+        STATUS_BUF[pos..pos + today_str.len()].copy_from_slice(today_str.as_bytes());
+        pos += today_str.len();
+        
+        let suffix = b" boops today";
+        STATUS_BUF[pos..pos + suffix.len()].copy_from_slice(suffix);
+        pos += suffix.len();
+        
+        let len = (pos - 4) as u32;
+        STATUS_BUF[..4].copy_from_slice(&len.to_le_bytes());
+        core::ptr::addr_of!(STATUS_BUF) as *const u8
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn plugin_start() {
     unsafe {
         RUNNING = true;
         LAST_BOOP_STATE = false;
-        TICK_COUNT = 0;
+        ELAPSED_MS = 0;
         LAST_CHATBOX_SEND = 0;
+        LAST_DAY_CHECK = 0;
         PENDING_BOOP = false;
     }
     log("Boop Counter plugin started");
@@ -418,16 +453,17 @@ pub extern "C" fn plugin_stop() {
 }
 
 #[no_mangle]
-pub extern "C" fn plugin_update() {
+pub extern "C" fn plugin_update(dt_ms: u32) {
     unsafe {
         if !RUNNING {
             return;
         }
         
-        TICK_COUNT += 1;
+        ELAPSED_MS += dt_ms as u64;
         
-        // Check for day change every ~10 minutes (6000 ticks at 100ms)
-        if TICK_COUNT % 6000 == 0 {
+        // Check for day change every 10 minutes
+        if ELAPSED_MS - LAST_DAY_CHECK >= 600_000 {
+            LAST_DAY_CHECK = ELAPSED_MS;
             let current_ts = get_unix_timestamp();
             if LAST_BOOP_TIMESTAMP > 0 && is_different_day(LAST_BOOP_TIMESTAMP, current_ts) {
                 TODAY_BOOPS = 0;
@@ -452,10 +488,10 @@ pub extern "C" fn plugin_update() {
             
             log("Boop counted!");
             
-            // Send chatbox message with 2-second cooldown (20 ticks at 100ms)
-            if TICK_COUNT - LAST_CHATBOX_SEND >= 20 {
+            // Send chatbox message with 2-second cooldown
+            if ELAPSED_MS - LAST_CHATBOX_SEND >= 2000 {
                 send_chatbox_message();
-                LAST_CHATBOX_SEND = TICK_COUNT;
+                LAST_CHATBOX_SEND = ELAPSED_MS;
             } else {
                 log("Chatbox on cooldown");
             }
@@ -465,7 +501,7 @@ pub extern "C" fn plugin_update() {
         if SEND_MSG_FLAG {
             SEND_MSG_FLAG = false;
             send_chatbox_message();
-            LAST_CHATBOX_SEND = TICK_COUNT;
+            LAST_CHATBOX_SEND = ELAPSED_MS;
         }
         
         if RESET_TODAY_FLAG {
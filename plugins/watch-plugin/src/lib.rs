@@ -5,10 +5,9 @@ use core::panic::PanicInfo;
 // Host functions
 extern "C" {
     fn get_system_time() -> u32;
-    fn osc_send_float(addr_ptr: *const u8, addr_len: u32, value: f32) -> i32;
+    fn osc_send_normalized(addr_ptr: *const u8, addr_len: u32, value: i32, max: i32) -> i32;
     fn log_info(msg_ptr: *const u8, msg_len: u32);
     fn log_error(msg_ptr: *const u8, msg_len: u32);
-    fn save_config(key_ptr: *const u8, key_len: u32, value_ptr: *const u8, value_len: u32);
     fn load_config(key_ptr: *const u8, key_len: u32) -> i32; // Returns ptr to value or 0
 }
 
@@ -17,9 +16,10 @@ static mut RUNNING: bool = false;
 static mut LAST_SECOND: u32 = 255;
 static mut LAST_MINUTE: u32 = 255;
 static mut LAST_HOUR: u32 = 255;
-static mut TICK_COUNT: u32 = 0;
-static mut LAST_MINUTE_SEND: u32 = 0;
-static mut LAST_HOUR_SEND: u32 = 0;
+// Milliseconds since plugin_start, summed from plugin_update's dt_ms
+static mut ELAPSED_MS: u64 = 0;
+static mut LAST_MINUTE_SEND: u64 = 0;
+static mut LAST_HOUR_SEND: u64 = 0;
 
 // Configuration storage
 static mut CONFIG_SECONDS: [u8; 128] = [0; 128];
@@ -32,22 +32,11 @@ static SECONDS_ADDR: &str = "/avatar/parameters/Time_Seconds";
 static MINUTES_ADDR: &str = "/avatar/parameters/Time_Minutes";
 static HOURS_ADDR: &str = "/avatar/parameters/Time_Hours";
 
-// Convert frame index to the exact 2-decimal float Unity expects
-// Unity truncates to 2 decimals then does floor(value * total_frames)
-// So we need: ceil(frame * 100 / total_frames) / 100
-fn frame_to_value(frame: u32, total_frames: u32) -> f32 {
-    if frame == 0 {
-        return 0.0;
-    }
-    // Integer ceil: (a + b - 1) / b
-    let numerator = frame * 100 + total_frames - 1;
-    let cents = numerator / total_frames; // This is ceil(frame*100/total_frames)
-    cents as f32 / 100.0
-}
-
-fn send_float(address: &str, value: f32) {
+// Send `frame` out of `total_frames` steps; the host does the 2-decimal rounding
+// Unity's animator expects (ceil(frame * 100 / total_frames) / 100)
+fn send_normalized(address: &str, frame: u32, total_frames: u32) {
     unsafe {
-        osc_send_float(address.as_ptr(), address.len() as u32, value);
+        osc_send_normalized(address.as_ptr(), address.len() as u32, frame as i32, total_frames as i32);
     }
 }
 
@@ -57,15 +46,6 @@ fn log(message: &str) {
     }
 }
 
-fn save_config_value(key: &str, value: &str) {
-    unsafe {
-        save_config(
-            key.as_ptr(), key.len() as u32,
-            value.as_ptr(), value.len() as u32
-        );
-    }
-}
-
 fn load_config_value(key: &str) -> Option<&'static str> {
     unsafe {
         let ptr = load_config(key.as_ptr(), key.len() as u32);
@@ -122,22 +102,29 @@ fn get_hours_addr() -> &'static str {
     }
 }
 
+// Host ABI version this plugin was built against
+#[no_mangle]
+pub extern "C" fn plugin_abi_version() -> u32 {
+    1
+}
+
 #[no_mangle]
 pub extern "C" fn plugin_info() -> *const u8 {
-    let json = r#"{"name":"Watch","version":"0.1.0","description":"Sends current time (seconds, minutes, hours) to VRChat"}"#;
+    let json = r#"{"name":"Watch","version":"0.1.0","description":"Sends current time (seconds, minutes, hours) to VRChat","capabilities":["sends_osc"]}"#;
     write_string(json)
 }
 
 #[no_mangle]
 pub extern "C" fn plugin_ui_config() -> *const u8 {
-    let json = r#"{"title":"Watch","elements":[{"Label":{"text":"Configure OSC addresses for time values"}},{"Separator":null},{"TextInput":{"id":"seconds","label":"Seconds:","default_value":"/avatar/parameters/Time_Seconds","placeholder":"OSC address"}},{"TextInput":{"id":"minutes","label":"Minutes:","default_value":"/avatar/parameters/Time_Minutes","placeholder":"OSC address"}},{"TextInput":{"id":"hours","label":"Hours:","default_value":"/avatar/parameters/Time_Hours","placeholder":"OSC address"}}]}"#;
+    let json = r#"{"title":"Watch","elements":[{"Label":{"text":"Configure OSC addresses for time values"}},{"Separator":null},{"TextInput":{"id":"seconds","label":"Seconds:","default_value":"/avatar/parameters/Time_Seconds","placeholder":"OSC address","osc_address":true}},{"TextInput":{"id":"minutes","label":"Minutes:","default_value":"/avatar/parameters/Time_Minutes","placeholder":"OSC address","osc_address":true}},{"TextInput":{"id":"hours","label":"Hours:","default_value":"/avatar/parameters/Time_Hours","placeholder":"OSC address","osc_address":true}}]}"#;
     write_string(json)
 }
 
 #[no_mangle]
 pub extern "C" fn plugin_load_config() {
-    // Load addresses from config
-    if let Some(addr) = load_config_value("seconds_address") {
+    // Load addresses from config. The host saves inputs under their id;
+    // "<id>_address" is what older versions of this plugin wrote.
+    if let Some(addr) = load_config_value("seconds").or_else(|| load_config_value("seconds_address")) {
         unsafe {
             let len = addr.len().min(127);
             CONFIG_SECONDS[..len].copy_from_slice(&addr.as_bytes()[..len]);
@@ -145,7 +132,7 @@ pub extern "C" fn plugin_load_config() {
         }
     }
     
-    if let Some(addr) = load_config_value("minutes_address") {
+    if let Some(addr) = load_config_value("minutes").or_else(|| load_config_value("minutes_address")) {
         unsafe {
             let len = addr.len().min(127);
             CONFIG_MINUTES[..len].copy_from_slice(&addr.as_bytes()[..len]);
@@ -153,7 +140,7 @@ pub extern "C" fn plugin_load_config() {
         }
     }
     
-    if let Some(addr) = load_config_value("hours_address") {
+    if let Some(addr) = load_config_value("hours").or_else(|| load_config_value("hours_address")) {
         unsafe {
             let len = addr.len().min(127);
             CONFIG_HOURS[..len].copy_from_slice(&addr.as_bytes()[..len]);
@@ -168,38 +155,10 @@ pub extern "C" fn plugin_ui_event(event_ptr: i32, event_len: i32) {
         let event_bytes = core::slice::from_raw_parts(event_ptr as *const u8, event_len as usize);
         let event_str = core::str::from_utf8_unchecked(event_bytes);
         
+        // The host has already stored the applied values under their input ids
         if event_str.contains("ApplySettings") {
-            if let Some(seconds_start) = event_str.find(r#""seconds",""#) {
-                if let Some(seconds_end) = event_str[seconds_start + 11..].find('"') {
-                    let addr = &event_str[seconds_start + 11..seconds_start + 11 + seconds_end];
-                    let len = addr.len().min(127);
-                    CONFIG_SECONDS[..len].copy_from_slice(&addr.as_bytes()[..len]);
-                    CONFIG_LENS.0 = len;
-                    save_config_value("seconds_address", addr);
-                }
-            }
-            
-            if let Some(minutes_start) = event_str.find(r#""minutes",""#) {
-                if let Some(minutes_end) = event_str[minutes_start + 11..].find('"') {
-                    let addr = &event_str[minutes_start + 11..minutes_start + 11 + minutes_end];
-                    let len = addr.len().min(127);
-                    CONFIG_MINUTES[..len].copy_from_slice(&addr.as_bytes()[..len]);
-                    CONFIG_LENS.1 = len;
-                    save_config_value("minutes_address", addr);
-                }
-            }
-            
-            if let Some(hours_start) = event_str.find(r#""hours",""#) {
-                if let Some(hours_end) = event_str[hours_start + 9..].find('"') {
-                    let addr = &event_str[hours_start + 9..hours_start + 9 + hours_end];
-                    let len = addr.len().min(127);
-                    CONFIG_HOURS[..len].copy_from_slice(&addr.as_bytes()[..len]);
-                    CONFIG_LENS.2 = len;
-                    save_config_value("hours_address", addr);
-                }
-            }
-            
-            log("Configuration saved");
+            plugin_load_config();
+            log("Configuration applied");
         }
     }
 }
@@ -211,7 +170,7 @@ pub extern "C" fn plugin_start() {
         LAST_SECOND = 255;
         LAST_MINUTE = 255;
         LAST_HOUR = 255;
-        TICK_COUNT = 0;
+        ELAPSED_MS = 0;
         LAST_MINUTE_SEND = 0;
         LAST_HOUR_SEND = 0;
     }
@@ -227,43 +186,40 @@ pub extern "C" fn plugin_stop() {
 }
 
 #[no_mangle]
-pub extern "C" fn plugin_update() {
+pub extern "C" fn plugin_update(dt_ms: u32) {
     unsafe {
         if !RUNNING {
             return;
         }
         
-        TICK_COUNT += 1;
+        ELAPSED_MS += dt_ms as u64;
         
         let (second, minute, hour) = get_current_time();
         
         // Send seconds every second (every time it changes)
         if second != LAST_SECOND {
-            let seconds_norm = frame_to_value(second, 60);
-            send_float(get_seconds_addr(), seconds_norm);
+            send_normalized(get_seconds_addr(), second, 60);
             LAST_SECOND = second;
         }
         
-        // Send minutes: immediately when value changes OR every 50 ticks (5 seconds)
+        // Send minutes: immediately when value changes OR every 5 seconds
         let minute_changed = minute != LAST_MINUTE;
-        let minute_interval_elapsed = TICK_COUNT - LAST_MINUTE_SEND >= 50;
+        let minute_interval_elapsed = ELAPSED_MS - LAST_MINUTE_SEND >= 5000;
         
         if minute_changed || minute_interval_elapsed {
-            let minutes_norm = frame_to_value(minute, 60);
-            send_float(get_minutes_addr(), minutes_norm);
+            send_normalized(get_minutes_addr(), minute, 60);
             LAST_MINUTE = minute;
-            LAST_MINUTE_SEND = TICK_COUNT;
+            LAST_MINUTE_SEND = ELAPSED_MS;
         }
         
-        // Send hours: immediately when value changes OR every 50 ticks (5 seconds)
+        // Send hours: immediately when value changes OR every 5 seconds
         let hour_changed = hour != LAST_HOUR;
-        let hour_interval_elapsed = TICK_COUNT - LAST_HOUR_SEND >= 50;
+        let hour_interval_elapsed = ELAPSED_MS - LAST_HOUR_SEND >= 5000;
         
         if hour_changed || hour_interval_elapsed {
-            let hours_norm = frame_to_value(hour, 24);
-            send_float(get_hours_addr(), hours_norm);
+            send_normalized(get_hours_addr(), hour, 24);
             LAST_HOUR = hour;
-            LAST_HOUR_SEND = TICK_COUNT;
+            LAST_HOUR_SEND = ELAPSED_MS;
         }
     }
 }
@@ -0,0 +1,85 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// VRChat's per-avatar OSC config, written to
+/// `LocalLow/VRChat/VRChat/OSC/<user id>/Avatars/<avatar id>.json`
+#[derive(Debug, Clone, Deserialize)]
+pub struct AvatarConfig {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    parameters: Vec<RawParameter>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct RawParameter {
+    name: String,
+    /// What VRChat accepts; missing for read-only parameters
+    #[serde(default)]
+    input: Option<Endpoint>,
+    /// What VRChat sends
+    #[serde(default)]
+    output: Option<Endpoint>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Endpoint {
+    address: String,
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+/// One avatar parameter with its OSC address and type ("float", "int" or "bool")
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AvatarParameter {
+    pub name: String,
+    pub address: String,
+    pub osc_type: String,
+}
+
+impl AvatarConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        // VRChat writes these files with a UTF-8 byte order mark
+        let content = content.trim_start_matches('\u{feff}');
+        serde_json::from_str(content)
+            .with_context(|| format!("{} is not a VRChat avatar OSC config", path.display()))
+    }
+    
+    pub fn parameters(&self) -> Vec<AvatarParameter> {
+        self.parameters
+            .iter()
+            .filter_map(|p| {
+                let endpoint = p.output.as_ref().or(p.input.as_ref())?;
+                Some(AvatarParameter {
+                    name: p.name.clone(),
+                    address: endpoint.address.clone(),
+                    osc_type: endpoint.kind.to_lowercase(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Where VRChat keeps its OSC configs, if that folder exists: under the user profile
+/// on Windows, or inside the Proton prefix for a Steam install on Linux
+pub fn default_osc_dir() -> Option<PathBuf> {
+    let vrchat_dir = Path::new("AppData").join("LocalLow").join("VRChat").join("VRChat").join("OSC");
+    
+    let candidates = if cfg!(windows) {
+        vec![dirs::home_dir()?.join(&vrchat_dir)]
+    } else {
+        let prefix = Path::new("steamapps/compatdata/438100/pfx/drive_c/users/steamuser");
+        vec![
+            dirs::data_dir()?.join("Steam").join(prefix).join(&vrchat_dir),
+            dirs::home_dir()?.join(".steam/steam").join(prefix).join(&vrchat_dir),
+        ]
+    };
+    
+    candidates.into_iter().find(|dir| dir.is_dir())
+}
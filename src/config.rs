@@ -1,57 +1,377 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::fs;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+static APP_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+// Environment variables that override config.toml. Only these three are supported;
+// precedence is command line > environment > config file > defaults.
+
+/// Overrides `osc.bind_address` for this run
+pub const ENV_BIND: &str = "FOXOSC_BIND";
+/// Overrides `osc.target_address` for this run
+pub const ENV_TARGET: &str = "FOXOSC_TARGET";
+/// Replaces `~/.config/fox-osc`, like `--config-dir`
+pub const ENV_CONFIG_DIR: &str = "FOXOSC_CONFIG_DIR";
+
+/// How long `mark_dirty` changes wait before `flush_if_due` writes them
+pub const SAVE_DELAY: Duration = Duration::from_secs(1);
+
+/// Profile that configs from before profiles existed load into; it can't be deleted
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Use `dir` instead of `~/.config/fox-osc` for the config file, plugins and plugin
+/// data. Only takes effect if called before anything has loaded; returns false otherwise.
+pub fn set_app_dir(dir: PathBuf) -> bool {
+    APP_DIR_OVERRIDE.set(dir).is_ok()
+}
+
+/// Root directory for config.toml, plugins/ and data/: the `set_app_dir` override,
+/// then `FOXOSC_CONFIG_DIR`, then the platform config dir
+pub fn app_dir() -> Result<PathBuf> {
+    let dir = match (APP_DIR_OVERRIDE.get(), env_override(ENV_CONFIG_DIR)) {
+        (Some(dir), _) => dir.clone(),
+        (None, Some(dir)) => PathBuf::from(dir),
+        (None, None) => dirs::config_dir()
+            .ok_or_else(|| anyhow::anyhow!("Failed to get config directory"))?
+            .join("fox-osc"),
+    };
+    // Freeze the choice so a late set_app_dir can't split files across two dirs
+    let _ = APP_DIR_OVERRIDE.set(dir.clone());
+    Ok(dir)
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
+    /// Plugin file names (e.g. "watch.wasm") to load first, in this order.
+    /// Anything not listed loads afterwards, sorted by file name. A plugin's
+    /// `depends_on` still moves it after the plugins it names.
+    #[serde(default)]
+    pub plugin_order: Vec<String>,
+    /// Directories scanned for .wasm plugins in addition to the default plugins folder,
+    /// e.g. a plugin crate's `target/wasm32-unknown-unknown/release`
+    #[serde(default)]
+    pub extra_plugin_dirs: Vec<PathBuf>,
+    /// How often plugin_update runs, in milliseconds. Plugins get the actual elapsed
+    /// time as `dt_ms`, so they should use that rather than assume this interval.
+    #[serde(default = "default_tick_interval_ms")]
+    pub tick_interval_ms: u64,
     pub osc: OscConfig,
     pub ui: UiConfig,
     #[serde(default)]
+    pub midi: MidiConfig,
+    #[serde(default)]
+    pub gamepad: GamepadConfig,
+    #[serde(default)]
+    pub websocket: WebSocketConfig,
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    /// Settings of the active profile's plugins
+    #[serde(default)]
     pub plugins: HashMap<String, PluginConfig>,
+    /// Name of the profile whose settings are currently in `plugins`
+    #[serde(default = "default_profile_name")]
+    pub active_profile: String,
+    /// The other profiles' plugin settings, swapped into `plugins` by `switch_profile`
+    #[serde(default)]
+    pub profiles: HashMap<String, ProfileData>,
+    /// Command-line and environment values for this run only; never written to the config file
+    #[serde(skip)]
+    pub overrides: ConfigOverrides,
+    // When the oldest change not yet written was made; see `mark_dirty`
+    #[serde(skip)]
+    dirty_since: Option<Instant>,
+}
+
+// Set and non-empty, so `FOXOSC_BIND=` in a script doesn't count
+fn env_override(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|value| !value.trim().is_empty())
+}
+
+/// Why startup fell back to default settings
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ConfigLoadError {
+    /// config.toml existed but wasn't valid. It was moved to `backup` if possible.
+    #[error("{message}\n\n{}", match backup {
+        Some(backup) => format!("The broken file was saved as {} and default settings were loaded.", backup.display()),
+        None => "It could not be backed up, so it was left in place and default settings were loaded for this run.".to_string(),
+    })]
+    Invalid {
+        /// The TOML parser's message, including the line and column
+        message: String,
+        backup: Option<PathBuf>,
+    },
+    /// There was no config.toml and a default one couldn't be written, e.g. on a
+    /// read-only home directory
+    #[error("Settings can't be saved: {0}\n\nChanges will be lost when the app closes.")]
+    Unwritable(String),
+}
+
+/// Settings given on the command line or in the environment. Precedence is
+/// CLI > environment > config file > defaults.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigOverrides {
+    pub bind_address: Option<String>,
+    pub target_address: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OscConfig {
+    /// "host:port" to listen on; IPv6 literals like "[::]:9001" are supported
     pub bind_address: String,
+    /// One or more comma-separated "host:port" destinations
     pub target_address: String,
+    /// Minimum time between `/chatbox/input` sends; VRChat drops faster messages
+    #[serde(default = "default_chatbox_interval_ms")]
+    pub chatbox_interval_ms: u64,
+    /// Optional "host:port" that every received packet is passed through to
+    #[serde(default)]
+    pub relay_address: Option<String>,
+    /// Set SO_REUSEADDR so a lingering socket from a previous run doesn't block binding
+    #[serde(default)]
+    pub reuse_address: bool,
+    /// Drop float sends that repeat the last value sent to the same address. Leave off
+    /// for avatars that rely on periodic re-sends (e.g. to sync late joiners).
+    #[serde(default)]
+    pub send_only_on_change: bool,
+    /// Bind `bind_address` and listen for incoming OSC. Turn off to use FoxOSC purely as
+    /// a sender, leaving the port free for other receivers; nothing is received at all then.
+    #[serde(default = "default_true")]
+    pub enable_receive: bool,
+    /// Log received packets' raw bytes as hex and ASCII, for debugging senders that
+    /// aren't VRChat. Off by default since "all" logs every packet.
+    #[serde(default)]
+    pub packet_dump: PacketDump,
+}
+
+/// Which received packets `osc.packet_dump` logs the bytes of
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PacketDump {
+    #[default]
+    Off,
+    /// Only packets that fail to decode
+    Failed,
+    All,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_chatbox_interval_ms() -> u64 {
+    1300
+}
+
+fn default_tick_interval_ms() -> u64 {
+    100
+}
+
+fn default_console_font_size() -> u32 {
+    10
+}
+
+fn default_stale_address_secs() -> u64 {
+    30
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UiConfig {
     pub console_enabled: bool,
+    /// Start with only the tray icon showing. Ignored in builds without the `tray` feature,
+    /// since there'd be no way to bring the window back.
+    #[serde(default)]
+    pub start_minimized: bool,
+    /// Wrap long lines in the console views instead of scrolling sideways
+    #[serde(default)]
+    pub console_wrap: bool,
+    /// Console font size in points
+    #[serde(default = "default_console_font_size")]
+    pub console_font_size: u32,
+    /// Active Addresses not updated for this many seconds are grayed out, and removed
+    /// by "Clear Stale"
+    #[serde(default = "default_stale_address_secs")]
+    pub stale_address_secs: u64,
+    #[serde(default)]
+    pub window: WindowConfig,
+}
+
+/// Main window geometry and tab, restored on the next launch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowConfig {
+    pub width: i32,
+    pub height: i32,
+    /// Index of the selected notebook page
+    pub last_tab: u32,
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self {
+            width: 800,
+            height: 600,
+            last_tab: 0,
+        }
+    }
+}
+
+/// WebSocket server that mirrors OSC traffic as JSON for browser tools
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebSocketConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// "host:port" to listen on; keep it on localhost unless other machines need it
+    #[serde(default = "default_websocket_bind_address")]
+    pub bind_address: String,
+}
+
+fn default_websocket_bind_address() -> String {
+    "127.0.0.1:9002".to_string()
+}
+
+impl Default for WebSocketConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: default_websocket_bind_address(),
+        }
+    }
+}
+
+/// Prometheus-style `/metrics` HTTP endpoint with OSC and plugin stats
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// "host:port" to listen on; keep it on localhost unless a remote scraper needs it
+    #[serde(default = "default_metrics_bind_address")]
+    pub bind_address: String,
+}
+
+fn default_metrics_bind_address() -> String {
+    "127.0.0.1:9101".to_string()
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind_address: default_metrics_bind_address(),
+        }
+    }
+}
+
+/// MIDI input bridge settings. Only used when built with the `midi` feature.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MidiConfig {
+    /// Name of the input port to open at startup
+    #[serde(default)]
+    pub port: Option<String>,
+    /// MIDI events that are forwarded to OSC addresses as 0..1 floats
+    #[serde(default)]
+    pub bindings: Vec<MidiBinding>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MidiBinding {
+    pub kind: MidiEventKind,
+    /// 1-16, or None to match any channel
+    #[serde(default)]
+    pub channel: Option<u8>,
+    /// CC or note number, 0-127
+    pub number: u8,
+    pub address: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MidiEventKind {
+    #[serde(rename = "cc")]
+    ControlChange,
+    #[serde(rename = "note")]
+    Note,
+}
+
+/// Gamepad input bridge settings. Only used when built with the `gamepad` feature.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GamepadConfig {
+    /// Controller inputs that are forwarded to OSC addresses as floats
+    #[serde(default)]
+    pub bindings: Vec<GamepadBinding>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GamepadBinding {
+    /// e.g. "axis/LeftStickX" or "button/RightTrigger2"; see `gamepad::INPUTS`
+    pub input: String,
+    pub address: String,
+}
+
+/// A named set of plugin settings, including each plugin's "enabled" flag and addresses
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfileData {
+    #[serde(default)]
+    pub plugins: HashMap<String, PluginConfig>,
+}
+
+fn default_profile_name() -> String {
+    DEFAULT_PROFILE.to_string()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PluginConfig {
     #[serde(default)]
     pub settings: HashMap<String, String>,
+    /// Binary values from save_blob, base64-encoded
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub blobs: HashMap<String, String>,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
+            plugin_order: Vec::new(),
+            extra_plugin_dirs: Vec::new(),
+            tick_interval_ms: default_tick_interval_ms(),
             osc: OscConfig {
                 bind_address: "0.0.0.0:9001".to_string(),
                 target_address: "127.0.0.1:9000".to_string(),
+                chatbox_interval_ms: default_chatbox_interval_ms(),
+                relay_address: None,
+                reuse_address: false,
+                send_only_on_change: false,
+                enable_receive: true,
+                packet_dump: PacketDump::Off,
             },
             ui: UiConfig {
                 console_enabled: true,
+                start_minimized: false,
+                console_wrap: false,
+                console_font_size: default_console_font_size(),
+                stale_address_secs: default_stale_address_secs(),
+                window: WindowConfig::default(),
             },
+            midi: MidiConfig::default(),
+            gamepad: GamepadConfig::default(),
+            websocket: WebSocketConfig::default(),
+            metrics: MetricsConfig::default(),
             plugins: HashMap::new(),
+            active_profile: default_profile_name(),
+            profiles: HashMap::new(),
+            overrides: ConfigOverrides::default(),
+            dirty_since: None,
         }
     }
 }
 
 impl Config {
-    fn config_path() -> Result<PathBuf> {
-        let config_dir = dirs::config_dir()
-            .ok_or_else(|| anyhow::anyhow!("Failed to get config directory"))?
-            .join("fox-osc");
-        
-        fs::create_dir_all(&config_dir)?;
-        Ok(config_dir.join("config.toml"))
+    pub fn config_path() -> Result<PathBuf> {
+        Ok(app_dir()?.join("config.toml"))
     }
     
     pub fn load() -> Result<Self> {
@@ -62,21 +382,202 @@ impl Config {
     }
     
     pub fn save(&self) -> Result<()> {
-        let path = Self::config_path()?;
+        self.save_to(&Self::config_path()?)
+    }
+    
+    fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create config directory {}", dir.display()))?;
+        }
         let content = toml::to_string_pretty(self)?;
-        fs::write(&path, content)?;
+        fs::write(path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(())
+    }
+    
+    /// Record a change to be written by a later `flush_if_due` or `flush` instead of
+    /// saving now, so something changing settings every tick costs one write per
+    /// `SAVE_DELAY` rather than one per change
+    pub fn mark_dirty(&mut self) {
+        self.dirty_since.get_or_insert_with(Instant::now);
+    }
+    
+    /// Whether there are changes `mark_dirty` hasn't written yet
+    pub fn is_dirty(&self) -> bool {
+        self.dirty_since.is_some()
+    }
+    
+    /// Write now, including anything waiting on `SAVE_DELAY`
+    pub fn flush(&mut self) -> Result<()> {
+        self.save()?;
+        self.dirty_since = None;
         Ok(())
     }
     
-    pub fn load_or_default() -> Result<Self> {
-        match Self::load() {
-            Ok(config) => Ok(config),
-            Err(_) => {
+    /// Flush if changes have been waiting at least `SAVE_DELAY`. After a failure the
+    /// next attempt waits another `SAVE_DELAY`, rather than retrying every call.
+    pub fn flush_if_due(&mut self) -> Result<()> {
+        match self.dirty_since {
+            Some(since) if since.elapsed() >= SAVE_DELAY => {
+                let result = self.flush();
+                if result.is_err() {
+                    self.dirty_since = Some(Instant::now());
+                }
+                result
+            }
+            _ => Ok(()),
+        }
+    }
+    
+    /// Load config.toml, or write and use defaults if there isn't one yet.
+    ///
+    /// A file that doesn't parse is moved to `config.toml.bak` rather than silently
+    /// overwritten. That, or not being able to write the defaults, is returned as a
+    /// `ConfigLoadError` so the user can be told. Read errors other than the file not
+    /// existing are returned as errors.
+    pub fn load_or_default() -> Result<(Self, Option<ConfigLoadError>)> {
+        Self::load_or_default_from(&Self::config_path()?)
+    }
+    
+    fn load_or_default_from(path: &Path) -> Result<(Self, Option<ConfigLoadError>)> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
                 let config = Self::default();
-                config.save()?;
-                Ok(config)
+                let error = config.save_to(path).err().map(|e| ConfigLoadError::Unwritable(format!("{:#}", e)));
+                return Ok((config.with_env_overrides(), error));
             }
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+        
+        match toml::from_str::<Config>(&content) {
+            Ok(config) => Ok((config.with_env_overrides(), None)),
+            Err(e) => {
+                // Only replace the file once the original is safe
+                let backup = path.with_extension("toml.bak");
+                let backup = fs::rename(path, &backup).ok().map(|_| backup);
+                
+                let config = Self::default();
+                if backup.is_some() {
+                    config.save_to(path)?;
+                }
+                let error = ConfigLoadError::Invalid { message: e.to_string(), backup };
+                Ok((config.with_env_overrides(), Some(error)))
+            }
+        }
+    }
+    
+    // FOXOSC_BIND and FOXOSC_TARGET, as overrides so they're never saved
+    fn with_env_overrides(mut self) -> Self {
+        if let Some(bind_address) = env_override(ENV_BIND) {
+            self.overrides.bind_address = Some(bind_address);
         }
+        if let Some(target_address) = env_override(ENV_TARGET) {
+            self.overrides.target_address = Some(target_address);
+        }
+        self
+    }
+    
+    /// Re-read config.toml, returning the new config if the file no longer matches this
+    /// one, e.g. after a hand edit. A file identical to what `save` would write is taken
+    /// as our own save and gives None. Command-line overrides carry over.
+    pub fn load_if_changed(&self) -> Result<Option<Self>> {
+        // The file is behind us until the next flush, which would overwrite any edit anyway
+        if self.is_dirty() {
+            return Ok(None);
+        }
+        
+        let path = Self::config_path()?;
+        let content = fs::read_to_string(&path)?;
+        if content == toml::to_string_pretty(self)? {
+            return Ok(None);
+        }
+        
+        let mut config: Config = toml::from_str(&content)?;
+        config.overrides = self.overrides.clone();
+        Ok(Some(config))
+    }
+    
+    /// OSC settings with any command-line overrides applied
+    pub fn effective_osc(&self) -> OscConfig {
+        let mut osc = self.osc.clone();
+        if let Some(bind_address) = &self.overrides.bind_address {
+            osc.bind_address = bind_address.clone();
+        }
+        if let Some(target_address) = &self.overrides.target_address {
+            osc.target_address = target_address.clone();
+        }
+        osc
+    }
+    
+    /// Whether the plugin should be running; plugins start enabled until switched off
+    pub fn plugin_enabled(&self, plugin_name: &str) -> bool {
+        self.get_plugin_setting(plugin_name, "enabled")
+            .map(|v| v != "false")
+            .unwrap_or(true)
+    }
+    
+    /// All profile names, the default first and the rest sorted
+    pub fn profile_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.profiles
+            .keys()
+            .chain(std::iter::once(&self.active_profile))
+            .filter(|name| *name != DEFAULT_PROFILE)
+            .cloned()
+            .collect();
+        names.sort();
+        names.dedup();
+        names.insert(0, DEFAULT_PROFILE.to_string());
+        names
+    }
+    
+    /// Copy the current plugin settings into a new profile and make it active
+    pub fn create_profile(&mut self, name: &str) -> Result<()> {
+        if name.is_empty() {
+            anyhow::bail!("Profile name is empty");
+        }
+        if self.profile_names().iter().any(|existing| existing == name) {
+            anyhow::bail!("Profile '{}' already exists", name);
+        }
+        
+        self.profiles.insert(name.to_string(), ProfileData { plugins: self.plugins.clone() });
+        self.switch_profile(name)
+    }
+    
+    /// Stash the current plugin settings under the active profile and load `name`'s.
+    /// The caller is responsible for re-applying them to running plugins.
+    pub fn switch_profile(&mut self, name: &str) -> Result<()> {
+        if name == self.active_profile {
+            return Ok(());
+        }
+        
+        let profile = match self.profiles.remove(name) {
+            Some(profile) => profile,
+            // The default profile has no entry until something else has been active
+            None if name == DEFAULT_PROFILE => ProfileData::default(),
+            None => anyhow::bail!("No profile named '{}'", name),
+        };
+        
+        let previous = std::mem::replace(&mut self.plugins, profile.plugins);
+        let previous_name = std::mem::replace(&mut self.active_profile, name.to_string());
+        self.profiles.insert(previous_name, ProfileData { plugins: previous });
+        Ok(())
+    }
+    
+    /// Delete a profile. Deleting the active one switches back to the default profile.
+    pub fn delete_profile(&mut self, name: &str) -> Result<()> {
+        if name == DEFAULT_PROFILE {
+            anyhow::bail!("The default profile can't be deleted");
+        }
+        
+        if name == self.active_profile {
+            self.plugins = self.profiles.remove(DEFAULT_PROFILE).unwrap_or_default().plugins;
+            self.active_profile = default_profile_name();
+        } else if self.profiles.remove(name).is_none() {
+            anyhow::bail!("No profile named '{}'", name);
+        }
+        Ok(())
     }
     
     pub fn get_plugin_setting(&self, plugin_name: &str, key: &str) -> Option<String> {
@@ -86,13 +587,82 @@ impl Config {
             .cloned()
     }
     
+    /// A blob stored with `set_plugin_blob`; None if missing or not valid base64
+    pub fn get_plugin_blob(&self, plugin_name: &str, key: &str) -> Option<Vec<u8>> {
+        self.plugins
+            .get(plugin_name)
+            .and_then(|p| p.blobs.get(key))
+            .and_then(|encoded| BASE64.decode(encoded).ok())
+    }
+    
+    pub fn set_plugin_blob(&mut self, plugin_name: &str, key: &str, value: &[u8]) {
+        let plugin_config = self.plugins
+            .entry(plugin_name.to_string())
+            .or_insert_with(|| PluginConfig {
+                settings: HashMap::new(),
+                blobs: HashMap::new(),
+            });
+        
+        plugin_config.blobs.insert(key.to_string(), BASE64.encode(value));
+    }
+    
     pub fn set_plugin_setting(&mut self, plugin_name: &str, key: &str, value: &str) {
         let plugin_config = self.plugins
             .entry(plugin_name.to_string())
             .or_insert_with(|| PluginConfig {
                 settings: HashMap::new(),
+                blobs: HashMap::new(),
             });
         
         plugin_config.settings.insert(key.to_string(), value.to_string());
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    // A fresh directory per test, so runs don't see each other's files
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("fox-osc-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+    
+    #[test]
+    fn malformed_config_is_kept_as_bak() {
+        let dir = temp_dir("malformed");
+        let path = dir.join("config.toml");
+        let broken = "tick_interval_ms = [not toml";
+        fs::write(&path, broken).unwrap();
+        
+        let (config, error) = Config::load_or_default_from(&path).unwrap();
+        
+        let backup = dir.join("config.toml.bak");
+        assert_eq!(fs::read_to_string(&backup).unwrap(), broken);
+        match error {
+            Some(ConfigLoadError::Invalid { backup: Some(reported), .. }) => assert_eq!(reported, backup),
+            other => panic!("expected an Invalid error with a backup, got {:?}", other),
+        }
+        // Defaults replace the broken file and are what's returned
+        assert_eq!(config.tick_interval_ms, default_tick_interval_ms());
+        assert!(toml::from_str::<Config>(&fs::read_to_string(&path).unwrap()).is_ok());
+        
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    
+    #[test]
+    fn missing_config_is_created_with_defaults() {
+        let dir = temp_dir("missing");
+        let path = dir.join("config.toml");
+        
+        let (_, error) = Config::load_or_default_from(&path).unwrap();
+        
+        assert!(error.is_none());
+        assert!(path.exists());
+        assert!(!dir.join("config.toml.bak").exists());
+        
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
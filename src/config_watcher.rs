@@ -0,0 +1,53 @@
+use anyhow::Result;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::ffi::OsString;
+use std::sync::mpsc::{self, Receiver};
+
+use crate::config::Config;
+
+/// Notices changes to config.toml made outside the app.
+///
+/// The directory is watched rather than the file, since editors often save by writing
+/// a new file and renaming it over the old one. Our own `Config::save` shows up here
+/// too; `Config::load_if_changed` is what tells those apart from real edits.
+pub struct ConfigWatcher {
+    // Stops watching when dropped
+    _watcher: RecommendedWatcher,
+    changes: Receiver<()>,
+}
+
+impl ConfigWatcher {
+    pub fn new() -> Result<Self> {
+        let path = Config::config_path()?;
+        let file_name: Option<OsString> = path.file_name().map(|name| name.to_os_string());
+        let dir = path.parent()
+            .ok_or_else(|| anyhow::anyhow!("Config path {} has no parent directory", path.display()))?
+            .to_path_buf();
+        
+        let (tx, changes) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |result: notify::Result<Event>| {
+            let event = match result {
+                Ok(event) => event,
+                Err(_) => return,
+            };
+            if matches!(event.kind, EventKind::Access(_)) {
+                return;
+            }
+            if event.paths.iter().any(|p| p.file_name() == file_name.as_deref()) {
+                let _ = tx.send(());
+            }
+        })?;
+        watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+        
+        Ok(Self { _watcher: watcher, changes })
+    }
+    
+    /// Whether config.toml was touched since the last call. Never blocks.
+    pub fn take_changed(&self) -> bool {
+        let mut changed = false;
+        while self.changes.try_recv().is_ok() {
+            changed = true;
+        }
+        changed
+    }
+}
@@ -1,23 +1,186 @@
 use gtk4::prelude::*;
-use gtk4::{TextView, ScrolledWindow, Box as GtkBox, Orientation, Notebook, Label, Switch, Paned, Widget};
+use gtk4::{
+    TextView, ScrolledWindow, Box as GtkBox, Orientation, Notebook, Label, Switch, Paned, Widget, Entry, Button,
+    CssProvider, DropDown, FileChooserAction, FileChooserDialog, FileFilter, ResponseType, StringList, StringObject,
+    Window, WrapMode, gdk,
+};
 use std::sync::Arc;
 use parking_lot::RwLock;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+use rosc::OscType;
 
+use crate::avatar_config::{self, AvatarConfig};
+use crate::config::Config;
+
+/// `source` is the name of the plugin that logged the message, or None for the app itself.
+/// The log view shows plugin entries as `[source] message` and app entries unprefixed.
 #[derive(Clone, Debug)]
 pub enum LogEntry {
-    Info(String),
-    Error(String),
+    Debug(String),
+    Info { message: String, source: Option<String> },
+    Warn(String),
+    Error { message: String, source: Option<String> },
     OscSent { address: String, value: String },
     OscReceived { address: String, value: String },
 }
 
+impl LogEntry {
+    /// OSC traffic counts as info
+    pub fn level(&self) -> LogLevel {
+        match self {
+            LogEntry::Debug(_) => LogLevel::Debug,
+            LogEntry::Warn(_) => LogLevel::Warn,
+            LogEntry::Error { .. } => LogLevel::Error,
+            LogEntry::Info { .. } | LogEntry::OscSent { .. } | LogEntry::OscReceived { .. } => LogLevel::Info,
+        }
+    }
+    
+    /// Plugin that logged this entry, if it came from one
+    pub fn source(&self) -> Option<&str> {
+        match self {
+            LogEntry::Info { source, .. } | LogEntry::Error { source, .. } => source.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Severity of a log entry, least severe first
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub const ALL: [LogLevel; 4] = [LogLevel::Debug, LogLevel::Info, LogLevel::Warn, LogLevel::Error];
+    
+    pub fn name(self) -> &'static str {
+        match self {
+            LogLevel::Debug => "Debug",
+            LogLevel::Info => "Info",
+            LogLevel::Warn => "Warn",
+            LogLevel::Error => "Error",
+        }
+    }
+}
+
+// Message rate is averaged over this window
+const RATE_WINDOW: Duration = Duration::from_secs(5);
+// Cap on remembered timestamps per address, so a flood can't grow memory unbounded
+const MAX_RATE_SAMPLES: usize = 1000;
+
+/// Latest value seen on an OSC address
+#[derive(Clone, Debug)]
+pub struct ActiveAddress {
+    pub args: Vec<OscType>,
+    pub last_updated: Instant,
+    recent: VecDeque<Instant>,
+}
+
+impl ActiveAddress {
+    fn new(args: &[OscType]) -> Self {
+        let now = Instant::now();
+        Self {
+            args: args.to_vec(),
+            last_updated: now,
+            recent: VecDeque::from([now]),
+        }
+    }
+    
+    fn update(&mut self, args: &[OscType]) {
+        let now = Instant::now();
+        self.args = args.to_vec();
+        self.last_updated = now;
+        
+        self.recent.push_back(now);
+        while self.recent.front().is_some_and(|t| now.duration_since(*t) > RATE_WINDOW)
+            || self.recent.len() > MAX_RATE_SAMPLES
+        {
+            self.recent.pop_front();
+        }
+    }
+    
+    /// Messages per second over the last few seconds; decays to 0 once updates stop
+    pub fn rate_hz(&self) -> f64 {
+        let now = Instant::now();
+        let count = self.recent.iter().filter(|t| now.duration_since(**t) <= RATE_WINDOW).count();
+        count as f64 / RATE_WINDOW.as_secs_f64()
+    }
+    
+    /// Compact type tag, e.g. "float" or "string,bool" for multi-arg messages
+    pub fn type_name(&self) -> String {
+        self.args.iter().map(osc_type_name).collect::<Vec<_>>().join(",")
+    }
+    
+    pub fn value_string(&self) -> String {
+        format_osc_args(&self.args)
+    }
+}
+
+pub fn osc_type_name(value: &OscType) -> &'static str {
+    match value {
+        OscType::Int(_) => "int",
+        OscType::Float(_) => "float",
+        OscType::String(_) => "string",
+        OscType::Blob(_) => "blob",
+        OscType::Time(_) => "time",
+        OscType::Long(_) => "long",
+        OscType::Double(_) => "double",
+        OscType::Char(_) => "char",
+        OscType::Color(_) => "color",
+        OscType::Midi(_) => "midi",
+        OscType::Bool(_) => "bool",
+        OscType::Array(_) => "array",
+        OscType::Nil => "nil",
+        OscType::Inf => "inf",
+    }
+}
+
+/// Human-readable value: floats to 2 decimals, strings quoted, colors as #rrggbbaa
+pub fn format_osc_value(value: &OscType) -> String {
+    match value {
+        OscType::Int(i) => i.to_string(),
+        OscType::Float(f) => format!("{:.2}", f),
+        OscType::String(s) => format!("\"{}\"", s),
+        OscType::Long(l) => l.to_string(),
+        OscType::Double(d) => format!("{:.2}", d),
+        OscType::Char(c) => c.to_string(),
+        OscType::Bool(b) => b.to_string(),
+        OscType::Color(c) => format!("#{:02x}{:02x}{:02x}{:02x}", c.red, c.green, c.blue, c.alpha),
+        OscType::Nil => "nil".to_string(),
+        OscType::Inf => "inf".to_string(),
+        OscType::Blob(bytes) => format!("<{} bytes>", bytes.len()),
+        // Seconds since 1900, fraction in units of 2^-32 s shown as microseconds
+        OscType::Time(t) => format!("{}.{:06}", t.seconds, (t.fractional as u64 * 1_000_000) >> 32),
+        OscType::Midi(m) => format!("midi {:02x} {:02x} {:02x} {:02x}", m.port, m.status, m.data1, m.data2),
+        OscType::Array(array) => format!("[{}]", format_osc_args(&array.content)),
+    }
+}
+
+/// All of a message's arguments, comma-separated, e.g. `"hello", true`
+pub fn format_osc_args(args: &[OscType]) -> String {
+    args.iter().map(format_osc_value).collect::<Vec<_>>().join(", ")
+}
+
+/// Type tag and formatted value for each argument, e.g. `[("string", "\"hi\""), ("bool", "true")]`,
+/// for consumers that want to show or export the two separately
+pub fn typed_osc_args(args: &[OscType]) -> Vec<(&'static str, String)> {
+    args.iter().map(|arg| (osc_type_name(arg), format_osc_value(arg))).collect()
+}
+
 pub struct ConsoleLog {
     enabled: bool,
     entries: Vec<LogEntry>,
     max_entries: usize,
-    active_addresses: HashMap<String, String>, // address -> current value
+    active_addresses: HashMap<String, ActiveAddress>, // address -> current value
+    known_addresses: HashMap<String, String>, // address -> type, from imported avatar configs
     last_displayed_count: usize, // Track how many entries we've displayed
+    min_display_level: LogLevel, // Entries below this are kept but not shown
+    source_filter: Option<String>, // When set, only this plugin's entries are shown
+    sources: Vec<String>, // Plugins that have logged anything, in order of their first entry
 }
 
 impl ConsoleLog {
@@ -27,7 +190,11 @@ impl ConsoleLog {
             entries: Vec::new(),
             max_entries: 1000,
             active_addresses: HashMap::new(),
+            known_addresses: HashMap::new(),
             last_displayed_count: 0,
+            min_display_level: LogLevel::Info,
+            source_filter: None,
+            sources: Vec::new(),
         }
     }
     
@@ -39,12 +206,72 @@ impl ConsoleLog {
         self.enabled
     }
     
+    pub fn min_display_level(&self) -> LogLevel {
+        self.min_display_level
+    }
+    
+    /// Hide entries below `level` in the log view. Call `reset_display_count` and clear
+    /// the views afterwards to apply it to entries already shown.
+    pub fn set_min_display_level(&mut self, level: LogLevel) {
+        self.min_display_level = level;
+    }
+    
+    pub fn source_filter(&self) -> Option<&str> {
+        self.source_filter.as_deref()
+    }
+    
+    /// Show only entries logged by the plugin `source`, or everything for None. Like
+    /// `set_min_display_level`, only affects entries displayed from now on.
+    pub fn set_source_filter(&mut self, source: Option<String>) {
+        self.source_filter = source;
+    }
+    
+    /// Names of plugins that have logged to the console, oldest first
+    pub fn sources(&self) -> &[String] {
+        &self.sources
+    }
+    
+    pub fn log_debug(&mut self, message: &str) {
+        if !self.enabled {
+            return;
+        }
+        
+        self.entries.push(LogEntry::Debug(message.to_string()));
+        if self.entries.len() > self.max_entries {
+            self.entries.remove(0);
+        }
+    }
+    
     pub fn log_info(&mut self, message: &str) {
         if !self.enabled {
             return;
         }
         
-        self.entries.push(LogEntry::Info(message.to_string()));
+        self.entries.push(LogEntry::Info { message: message.to_string(), source: None });
+        if self.entries.len() > self.max_entries {
+            self.entries.remove(0);
+        }
+    }
+    
+    /// `log_info` on behalf of a plugin, so the entry can be filtered by `source`
+    pub fn log_plugin_info(&mut self, source: &str, message: &str) {
+        if !self.enabled {
+            return;
+        }
+        
+        self.add_source(source);
+        self.entries.push(LogEntry::Info { message: message.to_string(), source: Some(source.to_string()) });
+        if self.entries.len() > self.max_entries {
+            self.entries.remove(0);
+        }
+    }
+    
+    pub fn log_warn(&mut self, message: &str) {
+        if !self.enabled {
+            return;
+        }
+        
+        self.entries.push(LogEntry::Warn(message.to_string()));
         if self.entries.len() > self.max_entries {
             self.entries.remove(0);
         }
@@ -55,12 +282,31 @@ impl ConsoleLog {
             return;
         }
         
-        self.entries.push(LogEntry::Error(message.to_string()));
+        self.entries.push(LogEntry::Error { message: message.to_string(), source: None });
         if self.entries.len() > self.max_entries {
             self.entries.remove(0);
         }
     }
     
+    /// `log_error` on behalf of a plugin, so the entry can be filtered by `source`
+    pub fn log_plugin_error(&mut self, source: &str, message: &str) {
+        if !self.enabled {
+            return;
+        }
+        
+        self.add_source(source);
+        self.entries.push(LogEntry::Error { message: message.to_string(), source: Some(source.to_string()) });
+        if self.entries.len() > self.max_entries {
+            self.entries.remove(0);
+        }
+    }
+    
+    fn add_source(&mut self, source: &str) {
+        if !self.sources.iter().any(|known| known == source) {
+            self.sources.push(source.to_string());
+        }
+    }
+    
     pub fn log_osc_sent(&mut self, address: &str, value: &str) {
         if !self.enabled {
             return;
@@ -76,17 +322,17 @@ impl ConsoleLog {
         }
     }
     
-    pub fn log_osc_received(&mut self, address: &str, value: &str) {
+    pub fn log_osc_received(&mut self, address: &str, args: &[OscType]) {
         if !self.enabled {
             return;
         }
         
         // Update active addresses
-        self.active_addresses.insert(address.to_string(), value.to_string());
+        self.record_active_address(address, args);
         
         self.entries.push(LogEntry::OscReceived {
             address: address.to_string(),
-            value: value.to_string(),
+            value: format_osc_args(args),
         });
         
         if self.entries.len() > self.max_entries {
@@ -95,9 +341,9 @@ impl ConsoleLog {
     }
     
     // Update active address without logging to entries (for unlistened addresses)
-    pub fn update_active_address(&mut self, address: &str, value: &str) {
+    pub fn update_active_address(&mut self, address: &str, args: &[OscType]) {
         // Only update active addresses map, don't add to log entries
-        self.active_addresses.insert(address.to_string(), value.to_string());
+        self.record_active_address(address, args);
     }
     
     pub fn get_entries(&self) -> &[LogEntry] {
@@ -114,15 +360,116 @@ impl ConsoleLog {
         self.last_displayed_count = 0;
     }
     
-    pub fn get_active_addresses(&self) -> &HashMap<String, String> {
+    fn record_active_address(&mut self, address: &str, args: &[OscType]) {
+        match self.active_addresses.get_mut(address) {
+            Some(entry) => entry.update(args),
+            None => {
+                self.active_addresses.insert(address.to_string(), ActiveAddress::new(args));
+            }
+        }
+    }
+    
+    pub fn get_active_addresses(&self) -> &HashMap<String, ActiveAddress> {
         &self.active_addresses
     }
     
+    /// Forget addresses not updated within `max_age`. Returns how many were removed.
+    pub fn remove_stale_addresses(&mut self, max_age: Duration) -> usize {
+        let before = self.active_addresses.len();
+        self.active_addresses.retain(|_, entry| entry.last_updated.elapsed() < max_age);
+        before - self.active_addresses.len()
+    }
+    
+    /// Remember addresses that exist but may not have been received yet, e.g. from an
+    /// imported avatar config. Returns how many were new.
+    pub fn add_known_addresses(&mut self, addresses: impl IntoIterator<Item = (String, String)>) -> usize {
+        let before = self.known_addresses.len();
+        self.known_addresses.extend(addresses);
+        self.known_addresses.len() - before
+    }
+    
+    pub fn get_known_addresses(&self) -> &HashMap<String, String> {
+        &self.known_addresses
+    }
+    
+    /// Every address we know of, received or imported, sorted
+    pub fn address_suggestions(&self) -> Vec<String> {
+        let mut addresses: Vec<String> = self.active_addresses.keys()
+            .chain(self.known_addresses.keys())
+            .cloned()
+            .collect();
+        addresses.sort();
+        addresses.dedup();
+        addresses
+    }
+    
     pub fn clear(&mut self) {
         self.entries.clear();
     }
 }
 
+/// `log` backend that keeps env_logger's terminal output and also forwards
+/// records into the GUI console at their own level, so library diagnostics
+/// (wasmtime, rosc, ...) show up next to the app's own messages. Info and above
+/// always reach the console; debug only when `RUST_LOG` asks for it.
+pub struct ConsoleLogger {
+    console: Arc<RwLock<ConsoleLog>>,
+    env_logger: env_logger::Logger,
+}
+
+impl ConsoleLogger {
+    pub fn install(console: Arc<RwLock<ConsoleLog>>) -> Result<(), log::SetLoggerError> {
+        let env_logger = env_logger::Builder::from_default_env().build();
+        let max_level = env_logger.filter().max(log::LevelFilter::Info);
+        
+        // Without RUST_LOG env_logger only passes errors, but the console has always shown info
+        if std::env::var_os("RUST_LOG").is_some() {
+            console.write().set_min_display_level(match env_logger.filter() {
+                log::LevelFilter::Off | log::LevelFilter::Error => LogLevel::Error,
+                log::LevelFilter::Warn => LogLevel::Warn,
+                log::LevelFilter::Info => LogLevel::Info,
+                log::LevelFilter::Debug | log::LevelFilter::Trace => LogLevel::Debug,
+            });
+        }
+        
+        log::set_boxed_logger(Box::new(Self { console, env_logger }))?;
+        log::set_max_level(max_level);
+        Ok(())
+    }
+}
+
+impl log::Log for ConsoleLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Info || self.env_logger.enabled(metadata)
+    }
+    
+    fn log(&self, record: &log::Record) {
+        let matches = self.env_logger.matches(record);
+        if matches {
+            self.env_logger.log(record);
+        }
+        
+        if record.level() > log::Level::Info && !matches {
+            return;
+        }
+        
+        // Skip rather than deadlock if the record was emitted while the console is locked
+        if let Some(mut console) = self.console.try_write() {
+            let message = format!("[{}] {}", record.target(), record.args());
+            match record.level() {
+                log::Level::Error => console.log_error(&message),
+                log::Level::Warn => console.log_warn(&message),
+                log::Level::Info => console.log_info(&message),
+                log::Level::Debug | log::Level::Trace => console.log_debug(&message),
+            }
+        }
+    }
+    
+    fn flush(&self) {
+        self.env_logger.flush();
+    }
+}
+
 pub struct ConsoleViews {
     pub unified_view: TextView,
     pub sent_view: TextView,
@@ -130,7 +477,14 @@ pub struct ConsoleViews {
     pub active_view: TextView,
 }
 
-pub fn create_console_ui(console: Arc<RwLock<ConsoleLog>>) -> (GtkBox, Switch, ConsoleViews) {
+// Text tag for Active Addresses rows that haven't updated in a while
+const STALE_TAG: &str = "stale";
+
+// Limits for the console font size buttons, in points
+const MIN_CONSOLE_FONT_SIZE: u32 = 6;
+const MAX_CONSOLE_FONT_SIZE: u32 = 32;
+
+pub fn create_console_ui(console: Arc<RwLock<ConsoleLog>>, config: Arc<RwLock<Config>>) -> (GtkBox, Switch, ConsoleViews) {
     let vbox = GtkBox::new(Orientation::Vertical, 5);
     vbox.set_margin_top(10);
     vbox.set_margin_bottom(10);
@@ -144,21 +498,113 @@ pub fn create_console_ui(console: Arc<RwLock<ConsoleLog>>) -> (GtkBox, Switch, C
     console_switch.set_active(console.read().is_enabled());
     header_box.append(&console_label);
     header_box.append(&console_switch);
+    
+    // Follow tail: on always scrolls to new lines, off never does
+    let follow_label = Label::new(Some("Follow tail:"));
+    follow_label.set_margin_start(20);
+    let follow_switch = Switch::new();
+    follow_switch.set_active(true);
+    header_box.append(&follow_label);
+    header_box.append(&follow_switch);
+    
+    let (wrap, font_size) = {
+        let config = config.read();
+        (config.ui.console_wrap, config.ui.console_font_size)
+    };
+    
+    let wrap_label = Label::new(Some("Wrap lines:"));
+    wrap_label.set_margin_start(20);
+    let wrap_switch = Switch::new();
+    wrap_switch.set_active(wrap);
+    header_box.append(&wrap_label);
+    header_box.append(&wrap_switch);
+    
+    let smaller_button = Button::with_label("A-");
+    smaller_button.set_margin_start(20);
+    smaller_button.set_tooltip_text(Some("Smaller console font"));
+    let larger_button = Button::with_label("A+");
+    larger_button.set_tooltip_text(Some("Larger console font"));
+    header_box.append(&smaller_button);
+    header_box.append(&larger_button);
+    
+    // Minimum level shown in the log; starts from RUST_LOG when it's set
+    let level_label = Label::new(Some("Level:"));
+    level_label.set_margin_start(20);
+    let level_names: Vec<&str> = LogLevel::ALL.iter().map(|level| level.name()).collect();
+    let level_dropdown = DropDown::from_strings(&level_names);
+    let current_level = console.read().min_display_level();
+    if let Some(index) = LogLevel::ALL.iter().position(|level| *level == current_level) {
+        level_dropdown.set_selected(index as u32);
+    }
+    header_box.append(&level_label);
+    header_box.append(&level_dropdown);
+    
+    // Narrow the log to one plugin's messages; plugins are added as they first log
+    let source_label = Label::new(Some("Source:"));
+    source_label.set_margin_start(20);
+    let source_list = StringList::new(&["All"]);
+    let source_dropdown = DropDown::new(Some(source_list.clone()), None::<gtk4::Expression>);
+    header_box.append(&source_label);
+    header_box.append(&source_dropdown);
     vbox.append(&header_box);
     
     // Notebook for tabs
     let notebook = Notebook::new();
     
     // Tab 1: Log with sorting
-    let (log_tab, sort_switch, unified_view, sent_view, received_view) = create_log_tab();
+    let (log_tab, sort_switch, filter_entry, unified_view, sent_view, received_view) = create_log_tab();
     notebook.append_page(&log_tab, Some(&Label::new(Some("Log"))));
     
     // Tab 2: Active Addresses
-    let (active_tab, active_view) = create_active_addresses_tab();
+    let (active_tab, active_filter, active_view) = create_active_addresses_tab(console.clone(), config.clone());
     notebook.append_page(&active_tab, Some(&Label::new(Some("Active Addresses"))));
     
     vbox.append(&notebook);
     
+    // Wrap and font size apply to every view and are saved in the ui config
+    let all_views = [unified_view.clone(), sent_view.clone(), received_view.clone(), active_view.clone()];
+    for view in &all_views {
+        view.add_css_class("console-view");
+    }
+    set_wrap(&all_views, wrap);
+    let font_provider = CssProvider::new();
+    if let Some(display) = gdk::Display::default() {
+        gtk4::style_context_add_provider_for_display(&display, &font_provider, gtk4::STYLE_PROVIDER_PRIORITY_APPLICATION);
+    }
+    set_font_size(&font_provider, font_size);
+    
+    let config_clone = config.clone();
+    let console_clone = console.clone();
+    let views_clone = all_views.clone();
+    wrap_switch.connect_state_set(move |_, wrap| {
+        set_wrap(&views_clone, wrap);
+        let mut config = config_clone.write();
+        config.ui.console_wrap = wrap;
+        if let Err(e) = config.save() {
+            console_clone.write().log_error(&format!("Failed to save config: {}", e));
+        }
+        glib::Propagation::Proceed
+    });
+    
+    for (button, step) in [(&smaller_button, -1i32), (&larger_button, 1)] {
+        let config_clone = config.clone();
+        let console_clone = console.clone();
+        let provider_clone = font_provider.clone();
+        button.connect_clicked(move |_| {
+            let mut config = config_clone.write();
+            let size = config.ui.console_font_size.saturating_add_signed(step)
+                .clamp(MIN_CONSOLE_FONT_SIZE, MAX_CONSOLE_FONT_SIZE);
+            if size == config.ui.console_font_size {
+                return;
+            }
+            config.ui.console_font_size = size;
+            set_font_size(&provider_clone, size);
+            if let Err(e) = config.save() {
+                console_clone.write().log_error(&format!("Failed to save config: {}", e));
+            }
+        });
+    }
+    
     let views = ConsoleViews {
         unified_view: unified_view.clone(),
         sent_view: sent_view.clone(),
@@ -172,23 +618,235 @@ pub fn create_console_ui(console: Arc<RwLock<ConsoleLog>>) -> (GtkBox, Switch, C
     let sent_clone = sent_view.clone();
     let received_clone = received_view.clone();
     let sort_clone = sort_switch.clone();
+    let filter_clone = filter_entry.clone();
+    let follow_clone = follow_switch.clone();
+    let source_list_clone = source_list.clone();
     
     glib::timeout_add_seconds_local(1, move || {
-        update_log_view(&console_clone, &unified_clone, &sent_clone, &received_clone, sort_clone.is_active());
+        sync_source_list(&console_clone, &source_list_clone);
+        update_log_view(&console_clone, &unified_clone, &sent_clone, &received_clone, sort_clone.is_active(), &filter_clone.text(), follow_clone.is_active());
         glib::ControlFlow::Continue
     });
     
+    // Changing the filter re-renders everything from the stored entries
+    let console_clone = console.clone();
+    let unified_clone = unified_view.clone();
+    let sent_clone = sent_view.clone();
+    let received_clone = received_view.clone();
+    let sort_clone = sort_switch.clone();
+    let follow_clone = follow_switch.clone();
+    filter_entry.connect_changed(move |entry| {
+        unified_clone.buffer().set_text("");
+        sent_clone.buffer().set_text("");
+        received_clone.buffer().set_text("");
+        console_clone.write().reset_display_count();
+        update_log_view(&console_clone, &unified_clone, &sent_clone, &received_clone, sort_clone.is_active(), &entry.text(), follow_clone.is_active());
+    });
+    
+    // So does changing the level
+    let console_clone = console.clone();
+    let unified_clone = unified_view.clone();
+    let sent_clone = sent_view.clone();
+    let received_clone = received_view.clone();
+    let sort_clone = sort_switch.clone();
+    let filter_clone = filter_entry.clone();
+    let follow_clone = follow_switch.clone();
+    level_dropdown.connect_selected_notify(move |dropdown| {
+        let level = match LogLevel::ALL.get(dropdown.selected() as usize) {
+            Some(level) => *level,
+            None => return,
+        };
+        unified_clone.buffer().set_text("");
+        sent_clone.buffer().set_text("");
+        received_clone.buffer().set_text("");
+        {
+            let mut console = console_clone.write();
+            console.set_min_display_level(level);
+            console.reset_display_count();
+        }
+        update_log_view(&console_clone, &unified_clone, &sent_clone, &received_clone, sort_clone.is_active(), &filter_clone.text(), follow_clone.is_active());
+    });
+    
+    // And changing the source
+    let console_clone = console.clone();
+    let unified_clone = unified_view.clone();
+    let sent_clone = sent_view.clone();
+    let received_clone = received_view.clone();
+    let sort_clone = sort_switch.clone();
+    let filter_clone = filter_entry.clone();
+    let follow_clone = follow_switch.clone();
+    source_dropdown.connect_selected_notify(move |dropdown| {
+        // Index 0 is "All"; the rest are plugin names
+        let source = match dropdown.selected() {
+            0 | gtk4::INVALID_LIST_POSITION => None,
+            _ => dropdown.selected_item().and_downcast::<StringObject>().map(|item| item.string().to_string()),
+        };
+        unified_clone.buffer().set_text("");
+        sent_clone.buffer().set_text("");
+        received_clone.buffer().set_text("");
+        {
+            let mut console = console_clone.write();
+            console.set_source_filter(source);
+            console.reset_display_count();
+        }
+        update_log_view(&console_clone, &unified_clone, &sent_clone, &received_clone, sort_clone.is_active(), &filter_clone.text(), follow_clone.is_active());
+    });
+    
+    // Turning follow back on jumps straight to the newest lines
+    let unified_clone = unified_view.clone();
+    let sent_clone = sent_view.clone();
+    let received_clone = received_view.clone();
+    follow_switch.connect_state_set(move |_, follow| {
+        if follow {
+            for view in [&unified_clone, &sent_clone, &received_clone] {
+                scroll_to_end(view);
+            }
+        }
+        glib::Propagation::Proceed
+    });
+    
     let console_clone2 = console.clone();
+    let config_clone = config.clone();
     let active_clone = active_view.clone();
+    let active_filter_clone = active_filter.clone();
     glib::timeout_add_seconds_local(1, move || {
-        update_active_addresses_view(&console_clone2, &active_clone);
+        let stale_after = Duration::from_secs(config_clone.read().ui.stale_address_secs);
+        update_active_addresses_view(&console_clone2, &active_clone, &active_filter_clone.text(), stale_after);
         glib::ControlFlow::Continue
     });
     
+    // Filter right away instead of on the next tick
+    let console_clone2 = console.clone();
+    let config_clone = config.clone();
+    let active_clone = active_view.clone();
+    active_filter.connect_changed(move |entry| {
+        let stale_after = Duration::from_secs(config_clone.read().ui.stale_address_secs);
+        update_active_addresses_view(&console_clone2, &active_clone, &entry.text(), stale_after);
+    });
+    
     (vbox, console_switch, views)
 }
 
-fn create_log_tab() -> (GtkBox, Switch, TextView, TextView, TextView) {
+// Append plugins that started logging since the last call. Sources are only ever added,
+// so existing positions, and with them the current selection, stay put.
+fn sync_source_list(console: &Arc<RwLock<ConsoleLog>>, list: &StringList) {
+    let console = console.read();
+    let shown = list.n_items().saturating_sub(1) as usize;
+    for source in console.sources().iter().skip(shown) {
+        list.append(source);
+    }
+}
+
+fn set_wrap(views: &[TextView], wrap: bool) {
+    let mode = if wrap { WrapMode::WordChar } else { WrapMode::None };
+    for view in views {
+        view.set_wrap_mode(mode);
+    }
+}
+
+fn set_font_size(provider: &CssProvider, size: u32) {
+    provider.load_from_data(&format!("textview.console-view {{ font-size: {}pt; }}", size));
+}
+
+// Pick a VRChat avatar OSC config and add its parameters to the known addresses
+fn import_avatar_config(parent: &impl IsA<Widget>, console: Arc<RwLock<ConsoleLog>>) {
+    let dialog = FileChooserDialog::new(
+        Some("Import Avatar Config"),
+        parent.root().and_downcast_ref::<Window>(),
+        FileChooserAction::Open,
+        &[("Cancel", ResponseType::Cancel), ("Import", ResponseType::Accept)],
+    );
+    dialog.set_modal(true);
+    
+    let filter = FileFilter::new();
+    filter.set_name(Some("Avatar OSC config (*.json)"));
+    filter.add_pattern("*.json");
+    dialog.add_filter(&filter);
+    
+    if let Some(dir) = avatar_config::default_osc_dir() {
+        let _ = dialog.set_current_folder(Some(&gio::File::for_path(dir)));
+    }
+    
+    dialog.connect_response(move |dialog, response| {
+        let path = dialog.file().and_then(|f| f.path());
+        dialog.close();
+        
+        let path = match (response, path) {
+            (ResponseType::Accept, Some(path)) => path,
+            _ => return,
+        };
+        
+        match AvatarConfig::load(&path) {
+            Ok(config) => {
+                let parameters = config.parameters();
+                let mut console = console.write();
+                let added = console.add_known_addresses(parameters.iter().map(|p| (p.address.clone(), p.osc_type.clone())));
+                console.log_info(&format!(
+                    "Imported {} parameter(s) from avatar '{}' ({} new)",
+                    parameters.len(), config.name, added
+                ));
+            }
+            Err(e) => console.write().log_error(&format!("Failed to import avatar config: {:#}", e)),
+        }
+    });
+    
+    dialog.present();
+}
+
+// Every active address with its latest arguments, sorted by address:
+// [{"address": "/avatar/parameters/X", "args": [{"type": "float", "value": "0.5"}], "rate_hz": 2.0}]
+fn active_addresses_json(console: &ConsoleLog) -> serde_json::Value {
+    let mut addresses: Vec<_> = console.get_active_addresses().iter().collect();
+    addresses.sort_by_key(|(address, _)| *address);
+    
+    let entries: Vec<serde_json::Value> = addresses
+        .into_iter()
+        .map(|(address, entry)| {
+            let args: Vec<serde_json::Value> = typed_osc_args(&entry.args)
+                .into_iter()
+                .map(|(osc_type, value)| serde_json::json!({ "type": osc_type, "value": value }))
+                .collect();
+            serde_json::json!({ "address": address, "args": args, "rate_hz": entry.rate_hz() })
+        })
+        .collect();
+    serde_json::Value::Array(entries)
+}
+
+// Save the current active addresses to a JSON file the user picks
+fn export_active_addresses(parent: &impl IsA<Widget>, console: Arc<RwLock<ConsoleLog>>) {
+    let dialog = FileChooserDialog::new(
+        Some("Export Active Addresses"),
+        parent.root().and_downcast_ref::<Window>(),
+        FileChooserAction::Save,
+        &[("Cancel", ResponseType::Cancel), ("Export", ResponseType::Accept)],
+    );
+    dialog.set_modal(true);
+    dialog.set_current_name("osc-addresses.json");
+    
+    dialog.connect_response(move |dialog, response| {
+        let path = dialog.file().and_then(|f| f.path());
+        dialog.close();
+        
+        let path = match (response, path) {
+            (ResponseType::Accept, Some(path)) => path,
+            _ => return,
+        };
+        
+        let json = active_addresses_json(&console.read());
+        let count = json.as_array().map_or(0, |entries| entries.len());
+        let result = serde_json::to_string_pretty(&json)
+            .map_err(anyhow::Error::from)
+            .and_then(|content| std::fs::write(&path, content).map_err(anyhow::Error::from));
+        match result {
+            Ok(()) => console.write().log_info(&format!("Exported {} address(es) to {}", count, path.display())),
+            Err(e) => console.write().log_error(&format!("Failed to export addresses: {}", e)),
+        }
+    });
+    
+    dialog.present();
+}
+
+fn create_log_tab() -> (GtkBox, Switch, Entry, TextView, TextView, TextView) {
     let vbox = GtkBox::new(Orientation::Vertical, 5);
     
     // Sort switch
@@ -198,6 +856,13 @@ fn create_log_tab() -> (GtkBox, Switch, TextView, TextView, TextView) {
     sort_switch.set_active(false);
     sort_box.append(&sort_label);
     sort_box.append(&sort_switch);
+    
+    // Filter box (case-insensitive substring match on rendered lines)
+    let filter_entry = Entry::new();
+    filter_entry.set_placeholder_text(Some("Filter log..."));
+    filter_entry.set_hexpand(true);
+    sort_box.append(&filter_entry);
+    
     sort_box.set_margin_bottom(5);
     vbox.append(&sort_box);
     
@@ -253,25 +918,79 @@ fn create_log_tab() -> (GtkBox, Switch, TextView, TextView, TextView) {
         glib::Propagation::Proceed
     });
     
-    (vbox, sort_switch, unified_view, sent_view, received_view)
+    (vbox, sort_switch, filter_entry, unified_view, sent_view, received_view)
 }
 
-fn create_active_addresses_tab() -> (ScrolledWindow, TextView) {
+fn create_active_addresses_tab(console: Arc<RwLock<ConsoleLog>>, config: Arc<RwLock<Config>>) -> (GtkBox, Entry, TextView) {
+    let vbox = GtkBox::new(Orientation::Vertical, 5);
+    
+    let button_box = GtkBox::new(Orientation::Horizontal, 10);
+    button_box.set_margin_bottom(5);
+    
+    let import_button = Button::with_label("Import Avatar Config…");
+    let console_clone = console.clone();
+    import_button.connect_clicked(move |button| {
+        import_avatar_config(button, console_clone.clone());
+    });
+    button_box.append(&import_button);
+    
+    let export_button = Button::with_label("Export Addresses…");
+    let console_clone = console.clone();
+    export_button.connect_clicked(move |button| {
+        export_active_addresses(button, console_clone.clone());
+    });
+    button_box.append(&export_button);
+    
+    // Drop addresses that have gone quiet, e.g. from a previous avatar
+    let clear_stale_button = Button::with_label("Clear Stale");
+    let stale_secs = config.read().ui.stale_address_secs;
+    clear_stale_button.set_tooltip_text(Some(&format!("Remove addresses not updated in the last {} seconds", stale_secs)));
+    clear_stale_button.connect_clicked(move |_| {
+        let stale_after = Duration::from_secs(config.read().ui.stale_address_secs);
+        let mut console = console.write();
+        let removed = console.remove_stale_addresses(stale_after);
+        console.log_info(&format!("Cleared {} stale address(es)", removed));
+    });
+    button_box.append(&clear_stale_button);
+    
+    // Case-insensitive substring match on the address
+    let filter_entry = Entry::new();
+    filter_entry.set_placeholder_text(Some("Filter addresses..."));
+    filter_entry.set_hexpand(true);
+    button_box.append(&filter_entry);
+    vbox.append(&button_box);
+    
     let scroll = ScrolledWindow::new();
     scroll.set_vexpand(true);
     
     let text_view = TextView::new();
     text_view.set_editable(false);
     text_view.set_monospace(true);
+    text_view.buffer().create_tag(Some(STALE_TAG), &[("foreground", &"gray")]);
     
     scroll.set_child(Some(&text_view));
-    (scroll, text_view)
+    vbox.append(&scroll);
+    (vbox, filter_entry, text_view)
+}
+
+fn format_log_entry(entry: &LogEntry) -> String {
+    match entry {
+        LogEntry::Debug(msg) => format!("· {}\n", msg),
+        LogEntry::Info { message, source: None } => format!("ℹ {}\n", message),
+        LogEntry::Info { message, source: Some(source) } => format!("ℹ [{}] {}\n", source, message),
+        LogEntry::Warn(msg) => format!("⚠ {}\n", msg),
+        LogEntry::Error { message, source: None } => format!("✗ {}\n", message),
+        LogEntry::Error { message, source: Some(source) } => format!("✗ [{}] {}\n", source, message),
+        LogEntry::OscSent { address, value } => format!("→ {} = {}\n", address, value),
+        LogEntry::OscReceived { address, value } => format!("← {} = {}\n", address, value),
+    }
 }
 
-fn update_log_view(console: &Arc<RwLock<ConsoleLog>>, unified_view: &TextView, sent_view: &TextView, received_view: &TextView, sorted: bool) {
-    let new_entries = {
+fn update_log_view(console: &Arc<RwLock<ConsoleLog>>, unified_view: &TextView, sent_view: &TextView, received_view: &TextView, sorted: bool, filter: &str, follow: bool) {
+    let (new_entries, min_level, source) = {
         let mut console_lock = console.write();
-        console_lock.get_new_entries().to_vec()
+        let source = console_lock.source_filter().map(str::to_string);
+        (console_lock.get_new_entries().to_vec(), console_lock.min_display_level(), source)
     };
     
     // If no new entries, nothing to do
@@ -279,117 +998,184 @@ fn update_log_view(console: &Arc<RwLock<ConsoleLog>>, unified_view: &TextView, s
         return;
     }
     
+    let filter = filter.to_lowercase();
+    let lines = new_entries.iter()
+        .filter(|entry| entry.level() >= min_level)
+        .filter(|entry| source.is_none() || entry.source() == source.as_deref())
+        .map(|entry| (entry, format_log_entry(entry)))
+        .filter(|(_, line)| filter.is_empty() || line.to_lowercase().contains(&filter));
+    
     if sorted {
         // Split mode - append to appropriate buffers
         let mut sent_text = String::new();
         let mut received_text = String::new();
         
-        for entry in &new_entries {
+        for (entry, line) in lines {
             match entry {
-                LogEntry::OscSent { address, value } => {
-                    sent_text.push_str(&format!("→ {} = {}\n", address, value));
-                }
-                LogEntry::OscReceived { address, value } => {
-                    received_text.push_str(&format!("← {} = {}\n", address, value));
-                }
-                LogEntry::Info(msg) => {
-                    sent_text.push_str(&format!("ℹ {}\n", msg));
-                }
-                LogEntry::Error(msg) => {
-                    sent_text.push_str(&format!("✗ {}\n", msg));
-                }
+                LogEntry::OscReceived { .. } => received_text.push_str(&line),
+                _ => sent_text.push_str(&line),
             }
         }
         
         // Append new text without clearing (no flicker!)
         if !sent_text.is_empty() {
-            append_text_with_smart_scroll(sent_view, &sent_text);
+            append_text(sent_view, &sent_text, follow);
         }
         if !received_text.is_empty() {
-            append_text_with_smart_scroll(received_view, &received_text);
+            append_text(received_view, &received_text, follow);
         }
     } else {
         // Unified mode - append all new entries
-        let mut text = String::new();
-        
-        for entry in &new_entries {
-            match entry {
-                LogEntry::Info(msg) => text.push_str(&format!("ℹ {}\n", msg)),
-                LogEntry::Error(msg) => text.push_str(&format!("✗ {}\n", msg)),
-                LogEntry::OscSent { address, value } => {
-                    text.push_str(&format!("→ {} = {}\n", address, value));
-                }
-                LogEntry::OscReceived { address, value } => {
-                    text.push_str(&format!("← {} = {}\n", address, value));
-                }
-            }
-        }
+        let text: String = lines.map(|(_, line)| line).collect();
         
         // Append new text
-        append_text_with_smart_scroll(unified_view, &text);
+        if !text.is_empty() {
+            append_text(unified_view, &text, follow);
+        }
     }
 }
 
-// Append text to TextView with smart scrolling (only auto-scroll if at bottom)
-fn append_text_with_smart_scroll(text_view: &TextView, text: &str) {
-    // Find the ScrolledWindow parent
-    let mut current = text_view.clone().upcast::<Widget>();
-    let mut scrolled_window: Option<ScrolledWindow> = None;
-    
-    while let Some(parent) = current.parent() {
-        if let Some(sw) = parent.downcast_ref::<ScrolledWindow>() {
-            scrolled_window = Some(sw.clone());
-            break;
-        }
-        current = parent;
-    }
-    
-    let should_auto_scroll = if let Some(sw) = &scrolled_window {
-        let vadj = sw.vadjustment();
-        let value = vadj.value();
-        let upper = vadj.upper();
-        let page_size = vadj.page_size();
-        
-        // Consider "at bottom" if within 50 pixels of the bottom
-        (value + page_size) >= (upper - 50.0)
-    } else {
-        false
-    };
-    
+// Append text to a TextView, scrolling to the new bottom only when following the tail
+fn append_text(text_view: &TextView, text: &str, follow: bool) {
     // Append the text to the end of buffer (no clearing!)
     let buffer = text_view.buffer();
     let mut end_iter = buffer.end_iter();
     buffer.insert(&mut end_iter, text);
     
-    // If we were at bottom, scroll to new bottom
-    if should_auto_scroll {
-        let text_view_clone = text_view.clone();
-        glib::idle_add_local_once(move || {
-            let buffer = text_view_clone.buffer();
-            let end_iter = buffer.end_iter();
-            text_view_clone.scroll_to_iter(&mut end_iter.clone(), 0.0, false, 0.0, 0.0);
-        });
+    if follow {
+        scroll_to_end(text_view);
     }
-    // If NOT at bottom, do nothing - position stays exactly where it is
 }
 
-fn update_active_addresses_view(console: &Arc<RwLock<ConsoleLog>>, view: &TextView) {
-    let active = console.read().get_active_addresses().clone();
+fn scroll_to_end(text_view: &TextView) {
+    // Wait for the new text to be laid out, or we'd scroll to the old bottom
+    let text_view_clone = text_view.clone();
+    glib::idle_add_local_once(move || {
+        let buffer = text_view_clone.buffer();
+        let end_iter = buffer.end_iter();
+        text_view_clone.scroll_to_iter(&mut end_iter.clone(), 0.0, false, 0.0, 0.0);
+    });
+}
+
+// Addresses not updated within stale_after are grayed out with STALE_TAG. Only addresses
+// containing `filter` (ignoring case) are listed.
+fn update_active_addresses_view(console: &Arc<RwLock<ConsoleLog>>, view: &TextView, filter: &str, stale_after: Duration) {
+    let filter = filter.trim().to_lowercase();
+    let matches = |address: &str| filter.is_empty() || address.to_lowercase().contains(&filter);
+    let (active, known) = {
+        let console = console.read();
+        (console.get_active_addresses().clone(), console.get_known_addresses().clone())
+    };
     
-    let mut buffer = String::new();
-    buffer.push_str("Active OSC Addresses (live values):\n");
-    buffer.push_str("═══════════════════════════════════\n\n");
+    let mut text = String::new();
+    text.push_str("Active OSC Addresses (live values):\n");
+    text.push_str("═══════════════════════════════════\n\n");
     
-    let mut sorted: Vec<_> = active.iter().collect();
+    let mut sorted: Vec<_> = active.iter().filter(|(addr, _)| matches(addr)).collect();
     sorted.sort_by_key(|(addr, _)| *addr);
     
-    for (address, value) in sorted {
-        buffer.push_str(&format!("{:<50} = {}\n", address, value));
+    text.push_str(&format!("{:<50} {:<8} {:<20} {:>7} {}\n", "Address", "Type", "Value", "Hz", "Updated"));
+    let buffer = view.buffer();
+    buffer.set_text(&text);
+    
+    let mut live = String::new();
+    for (address, entry) in sorted {
+        let age = entry.last_updated.elapsed();
+        let line = format!("{:<50} {:<8} {:<20} {:>7.1} {:.1}s ago\n", address, entry.type_name(), entry.value_string(), entry.rate_hz(), age.as_secs_f32());
+        if age < stale_after {
+            live.push_str(&line);
+            continue;
+        }
+        
+        // Flush the live lines before this one so they keep their order
+        buffer.insert(&mut buffer.end_iter(), &live);
+        live.clear();
+        buffer.insert_with_tags_by_name(&mut buffer.end_iter(), &line, &[STALE_TAG]);
+    }
+    
+    // Imported addresses that haven't been received yet
+    let mut pending: Vec<_> = known.iter().filter(|(addr, _)| !active.contains_key(*addr) && matches(addr)).collect();
+    pending.sort_by_key(|(addr, _)| *addr);
+    for (address, osc_type) in pending {
+        live.push_str(&format!("{:<50} {:<8} {:<20} {:>7} never\n", address, osc_type, "-", "-"));
+    }
+    
+    if active.is_empty() && known.is_empty() {
+        live.push_str("\n(No OSC addresses received yet)\n");
+    }
+    
+    buffer.insert(&mut buffer.end_iter(), &live);
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rosc::{OscArray, OscColor, OscMidiMessage, OscTime};
+    
+    #[test]
+    fn formats_numbers() {
+        assert_eq!(format_osc_value(&OscType::Int(-7)), "-7");
+        assert_eq!(format_osc_value(&OscType::Long(1 << 40)), "1099511627776");
+        assert_eq!(format_osc_value(&OscType::Float(0.126)), "0.13");
+        assert_eq!(format_osc_value(&OscType::Double(2.0)), "2.00");
+    }
+    
+    #[test]
+    fn formats_text_and_flags() {
+        assert_eq!(format_osc_value(&OscType::String("hi".to_string())), "\"hi\"");
+        assert_eq!(format_osc_value(&OscType::Char('x')), "x");
+        assert_eq!(format_osc_value(&OscType::Bool(true)), "true");
+        assert_eq!(format_osc_value(&OscType::Nil), "nil");
+        assert_eq!(format_osc_value(&OscType::Inf), "inf");
+    }
+    
+    #[test]
+    fn formats_blob_as_length() {
+        assert_eq!(format_osc_value(&OscType::Blob(vec![1, 2, 3])), "<3 bytes>");
     }
     
-    if active.is_empty() {
-        buffer.push_str("\n(No OSC addresses received yet)\n");
+    #[test]
+    fn formats_time_as_seconds_and_microseconds() {
+        // 2^31 units of 2^-32 s is half a second
+        let time = OscTime { seconds: 3_900_000_000, fractional: 1 << 31 };
+        assert_eq!(format_osc_value(&OscType::Time(time)), "3900000000.500000");
+        let time = OscTime { seconds: 1, fractional: 0 };
+        assert_eq!(format_osc_value(&OscType::Time(time)), "1.000000");
     }
     
-    view.buffer().set_text(&buffer);
-}
\ No newline at end of file
+    #[test]
+    fn formats_color_as_hex_rgba() {
+        let color = OscColor { red: 0xff, green: 0x80, blue: 0x00, alpha: 0x0a };
+        assert_eq!(format_osc_value(&OscType::Color(color)), "#ff80000a");
+    }
+    
+    #[test]
+    fn formats_midi_as_hex_bytes() {
+        let midi = OscMidiMessage { port: 0, status: 0x90, data1: 60, data2: 127 };
+        assert_eq!(format_osc_value(&OscType::Midi(midi)), "midi 00 90 3c 7f");
+    }
+    
+    #[test]
+    fn formats_arrays_recursively() {
+        let inner = OscType::Array(OscArray { content: vec![OscType::Bool(false)] });
+        let array = OscType::Array(OscArray { content: vec![OscType::Int(1), OscType::String("a".to_string()), inner] });
+        assert_eq!(format_osc_value(&array), "[1, \"a\", [false]]");
+        assert_eq!(format_osc_value(&OscType::Array(OscArray { content: Vec::new() })), "[]");
+    }
+    
+    #[test]
+    fn typed_args_pair_type_names_with_values() {
+        let args = [
+            OscType::String("hi".to_string()),
+            OscType::Bool(true),
+            OscType::Color(OscColor { red: 1, green: 2, blue: 3, alpha: 4 }),
+            OscType::Array(OscArray { content: vec![OscType::Float(1.0)] }),
+        ];
+        assert_eq!(typed_osc_args(&args), vec![
+            ("string", "\"hi\"".to_string()),
+            ("bool", "true".to_string()),
+            ("color", "#01020304".to_string()),
+            ("array", "[1.00]".to_string()),
+        ]);
+        assert_eq!(format_osc_args(&args), "\"hi\", true, #01020304, [1.00]");
+    }
+}
@@ -0,0 +1,158 @@
+use gilrs::{EventType, Gilrs};
+use parking_lot::{Mutex, RwLock};
+use rosc::OscType;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::config::Config;
+use crate::console::ConsoleLog;
+use crate::osc_manager::OscManager;
+
+// How long the input thread waits for an event before checking for shutdown
+const POLL_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Inputs that can be bound, as `axis/<name>` or `button/<name>` using gilrs' names.
+/// Sticks are -1..1; buttons, including the analog triggers, are 0..1.
+pub const INPUTS: &[&str] = &[
+    "axis/LeftStickX",
+    "axis/LeftStickY",
+    "axis/RightStickX",
+    "axis/RightStickY",
+    "button/LeftTrigger2",
+    "button/RightTrigger2",
+    "button/LeftTrigger",
+    "button/RightTrigger",
+    "button/South",
+    "button/East",
+    "button/North",
+    "button/West",
+    "button/DPadUp",
+    "button/DPadDown",
+    "button/DPadLeft",
+    "button/DPadRight",
+    "button/LeftThumb",
+    "button/RightThumb",
+    "button/Select",
+    "button/Start",
+    "button/Mode",
+];
+
+/// Address plugins can listen on for an input, e.g. `/gamepad/axis/LeftStickX`
+pub fn osc_address(input: &str) -> String {
+    format!("/gamepad/{}", input)
+}
+
+/// Reads connected controllers on a background thread and turns their input into OSC.
+///
+/// Every change is dispatched locally on `/gamepad/axis/<name>` or `/gamepad/button/<name>`
+/// so plugins can listen for it, and inputs with a binding in `config.gamepad.bindings`
+/// are also sent out as a float to the bound address. Inputs from all controllers are
+/// treated alike.
+pub struct GamepadBridge {
+    shutdown: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+    gamepads: Arc<Mutex<Vec<String>>>,
+    last_input: Arc<Mutex<Option<String>>>,
+}
+
+impl GamepadBridge {
+    pub fn start(
+        osc_manager: Arc<OscManager>,
+        console: Arc<RwLock<ConsoleLog>>,
+        app_config: Arc<RwLock<Config>>,
+    ) -> Self {
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let gamepads = Arc::new(Mutex::new(Vec::new()));
+        let last_input = Arc::new(Mutex::new(None));
+        
+        let shutdown_clone = shutdown.clone();
+        let gamepads_clone = gamepads.clone();
+        let last_input_clone = last_input.clone();
+        let thread = thread::spawn(move || {
+            // gilrs isn't Send, so it lives entirely on this thread
+            let mut gilrs = match Gilrs::new() {
+                Ok(gilrs) => gilrs,
+                Err(e) => {
+                    console.write().log_error(&format!("Gamepad input unavailable: {}", e));
+                    return;
+                }
+            };
+            Self::refresh_gamepads(&gilrs, &gamepads_clone);
+            
+            while !shutdown_clone.load(Ordering::Relaxed) {
+                let event = match gilrs.next_event_blocking(Some(POLL_TIMEOUT)) {
+                    Some(event) => event,
+                    None => continue,
+                };
+                
+                let (input, value) = match event.event {
+                    EventType::AxisChanged(axis, value, _) => (format!("axis/{:?}", axis), value),
+                    EventType::ButtonChanged(button, value, _) => (format!("button/{:?}", button), value),
+                    EventType::Connected | EventType::Disconnected => {
+                        Self::refresh_gamepads(&gilrs, &gamepads_clone);
+                        continue;
+                    }
+                    _ => continue,
+                };
+                
+                *last_input_clone.lock() = Some(input.clone());
+                Self::handle_input(&input, value, &osc_manager, &console, &app_config);
+            }
+        });
+        
+        Self {
+            shutdown,
+            thread: Some(thread),
+            gamepads,
+            last_input,
+        }
+    }
+    
+    /// Names of the controllers currently connected
+    pub fn gamepads(&self) -> Vec<String> {
+        self.gamepads.lock().clone()
+    }
+    
+    /// The input that most recently changed, to help pick one to bind
+    pub fn last_input(&self) -> Option<String> {
+        self.last_input.lock().clone()
+    }
+    
+    fn refresh_gamepads(gilrs: &Gilrs, gamepads: &Mutex<Vec<String>>) {
+        *gamepads.lock() = gilrs.gamepads().map(|(_, gamepad)| gamepad.name().to_string()).collect();
+    }
+    
+    // Runs on the input thread
+    fn handle_input(
+        input: &str,
+        value: f32,
+        osc_manager: &OscManager,
+        console: &RwLock<ConsoleLog>,
+        app_config: &RwLock<Config>,
+    ) {
+        osc_manager.dispatch_local(&osc_address(input), vec![OscType::Float(value)]);
+        
+        let addresses: Vec<String> = app_config.read().gamepad.bindings
+            .iter()
+            .filter(|b| b.input == input)
+            .map(|b| b.address.clone())
+            .collect();
+        
+        for address in addresses {
+            if let Err(e) = osc_manager.send_float(&address, value) {
+                console.write().log_error(&format!("Gamepad to OSC send failed: {}", e));
+            }
+        }
+    }
+}
+
+impl Drop for GamepadBridge {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
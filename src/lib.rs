@@ -1,8 +1,18 @@
+pub mod avatar_config;
 pub mod config;
+pub mod config_watcher;
 pub mod console;
+#[cfg(feature = "gamepad")]
+pub mod gamepad;
+#[cfg(feature = "midi")]
+pub mod midi;
+pub mod metrics;
 pub mod osc_manager;
 pub mod plugin_api;
+#[cfg(feature = "tray")]
+pub mod tray;
 pub mod wasm_loader;
+pub mod websocket;
 pub mod ui;
 
 use anyhow::Result;
@@ -10,7 +20,7 @@ use std::sync::Arc;
 use parking_lot::RwLock;
 
 pub use console::ConsoleLog;
-pub use config::Config;
+pub use config::{Config, ConfigLoadError};
 pub use wasm_loader::{WasmPluginLoader, WasmPlugin};
 
 /// Main application state
@@ -18,22 +28,21 @@ pub struct AppState {
     pub config: Arc<RwLock<Config>>,
     pub console: Arc<RwLock<ConsoleLog>>,
     pub plugin_loader: Arc<RwLock<WasmPluginLoader>>,
+    /// Set if config.toml was invalid and had to be replaced with defaults at startup
+    pub config_load_error: Option<ConfigLoadError>,
 }
 
 impl AppState {
+    /// Load the config and set up the plugin loader. There's deliberately no `Default`
+    /// impl, so a failure here always reaches the caller instead of panicking.
     pub fn new() -> Result<Self> {
-        let config = Config::load_or_default()?;
+        let (config, config_load_error) = Config::load_or_default()?;
         
         Ok(Self {
             config: Arc::new(RwLock::new(config)),
             console: Arc::new(RwLock::new(ConsoleLog::new())),
             plugin_loader: Arc::new(RwLock::new(WasmPluginLoader::new()?)),
+            config_load_error,
         })
     }
 }
-
-impl Default for AppState {
-    fn default() -> Self {
-        Self::new().unwrap()
-    }
-}
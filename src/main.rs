@@ -1,50 +1,226 @@
 use gtk4::prelude::*;
-use gtk4::Application;
+use gtk4::{Application, ButtonsType, MessageDialog, MessageType, ResponseType};
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use anyhow::Result;
 
-use osc_app_core::{AppState, osc_manager::OscManager, ui::MainWindow};
+use osc_app_core::{AppState, ConfigLoadError, console::ConsoleLogger, osc_manager::{OscManager, OscError}, ui::MainWindow, metrics, websocket};
+
+// Smallest tick interval we accept from config, to keep a typo from pinning the CPU
+const MIN_TICK_INTERVAL_MS: u64 = 10;
+
+/// Command-line options. Precedence for any setting is CLI > environment (`FOXOSC_BIND`,
+/// `FOXOSC_TARGET`, `FOXOSC_CONFIG_DIR`) > config file > defaults.
+///
+/// ```text
+/// fox-osc [--bind HOST:PORT] [--target HOST:PORT[,HOST:PORT...]]
+///         [--config-dir DIR] [--plugin-dir DIR]...
+/// ```
+#[derive(Default)]
+struct CliArgs {
+    bind_address: Option<String>,
+    target_address: Option<String>,
+    config_dir: Option<PathBuf>,
+    plugin_dirs: Vec<PathBuf>,
+}
 
 fn main() -> Result<()> {
-    env_logger::init();
+    let (cli, gtk_args) = parse_args(std::env::args());
+    
+    // Must happen before anything reads the config or plugin directories
+    if let Some(dir) = cli.config_dir.clone() {
+        osc_app_core::config::set_app_dir(dir);
+    }
     
     // Initialize GTK
     let app = Application::builder()
         .application_id("com.example.fox-osc")
         .build();
     
-    app.connect_activate(|app| {
-        if let Err(e) = setup_app(app) {
-            eprintln!("Failed to setup application: {}", e);
-            std::process::exit(1);
+    app.connect_activate(move |app| {
+        if let Err(e) = setup_app(app, &cli) {
+            eprintln!("Failed to setup application: {:#}", e);
+            show_startup_error_dialog(app, &e);
         }
     });
     
-    app.run();
+    // GTK rejects options it doesn't know, so only hand it the rest
+    app.run_with_args(&gtk_args);
     
     Ok(())
 }
 
-fn setup_app(app: &Application) -> Result<()> {
+// Pull our options out of the arguments, accepting both `--flag value` and `--flag=value`
+fn parse_args(mut args: impl Iterator<Item = String>) -> (CliArgs, Vec<String>) {
+    let mut cli = CliArgs::default();
+    let mut rest = Vec::new();
+    
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+            _ => (arg.clone(), None),
+        };
+        if !matches!(flag.as_str(), "--bind" | "--target" | "--config-dir" | "--plugin-dir") {
+            rest.push(arg);
+            continue;
+        }
+        
+        let value = match inline_value.or_else(|| args.next()) {
+            Some(value) => value,
+            None => {
+                eprintln!("{} needs a value", flag);
+                continue;
+            }
+        };
+        match flag.as_str() {
+            "--bind" => cli.bind_address = Some(value),
+            "--target" => cli.target_address = Some(value),
+            "--config-dir" => cli.config_dir = Some(PathBuf::from(value)),
+            _ => cli.plugin_dirs.push(PathBuf::from(value)),
+        }
+    }
+    
+    (cli, rest)
+}
+
+fn setup_app(app: &Application, cli: &CliArgs) -> Result<()> {
     // Create application state
     let app_state = Arc::new(AppState::new()?);
     
+    {
+        // On top of any environment overrides applied at load
+        let mut config = app_state.config.write();
+        if let Some(bind_address) = &cli.bind_address {
+            config.overrides.bind_address = Some(bind_address.clone());
+        }
+        if let Some(target_address) = &cli.target_address {
+            config.overrides.target_address = Some(target_address.clone());
+        }
+    }
+    
+    {
+        let mut loader = app_state.plugin_loader.write();
+        for dir in &cli.plugin_dirs {
+            loader.add_plugin_dir(dir.clone());
+        }
+    }
+    
+    // Route `log` records to the terminal and the GUI console
+    if let Err(e) = ConsoleLogger::install(app_state.console.clone()) {
+        eprintln!("Failed to install logger: {}", e);
+    }
+    
     // Set console enabled from config
     {
         let config = app_state.config.read();
         app_state.console.write().set_enabled(config.ui.console_enabled);
     }
     
-    // Initialize OSC manager
-    let config = app_state.config.read();
-    let osc_manager = Arc::new(OscManager::new(
-        &config.osc.bind_address,
-        &config.osc.target_address,
-        app_state.console.clone(),
-    )?);
-    drop(config);
+    // Defaults are in use because config.toml was broken or can't be written; say why
+    if let Some(error) = &app_state.config_load_error {
+        app_state.console.write().log_error(&format!("config.toml: {}", error));
+        show_config_error_dialog(app, error);
+    }
+    
+    start_osc(app, app_state)
+}
+
+// Nothing else is running yet, so the only way out is Quit
+fn show_startup_error_dialog(app: &Application, error: &anyhow::Error) {
+    let dialog = MessageDialog::builder()
+        .application(app)
+        .modal(true)
+        .message_type(MessageType::Error)
+        .buttons(ButtonsType::None)
+        .text("Fox OSC could not start")
+        .secondary_text(format!("{:#}", error))
+        .build();
+    dialog.add_button("Quit", ResponseType::Close);
+    
+    let app_clone = app.clone();
+    dialog.connect_response(move |dialog, _| {
+        dialog.close();
+        app_clone.quit();
+    });
+    dialog.present();
+}
+
+fn show_config_error_dialog(app: &Application, error: &ConfigLoadError) {
+    let dialog = MessageDialog::builder()
+        .application(app)
+        .modal(true)
+        .message_type(MessageType::Warning)
+        .buttons(ButtonsType::Ok)
+        .text("Problem loading config.toml")
+        .secondary_text(error.to_string())
+        .build();
+    dialog.connect_response(|dialog, _| dialog.close());
+    dialog.present();
+}
+
+// Bind the OSC socket, or explain why it failed instead of exiting
+fn start_osc(app: &Application, app_state: Arc<AppState>) -> Result<()> {
+    let osc_config = app_state.config.read().effective_osc();
+    
+    match OscManager::new(&osc_config, app_state.console.clone()) {
+        Ok(osc_manager) => start_app(app, app_state, Arc::new(osc_manager)),
+        Err(e) => {
+            show_bind_error_dialog(app, app_state, &osc_config.bind_address, e);
+            Ok(())
+        }
+    }
+}
+
+fn show_bind_error_dialog(app: &Application, app_state: Arc<AppState>, bind_address: &str, error: anyhow::Error) {
+    let dialog = MessageDialog::builder()
+        .application(app)
+        .modal(true)
+        .message_type(MessageType::Error)
+        .buttons(ButtonsType::None)
+        .text("Could not start OSC")
+        .secondary_text(error.to_string())
+        .build();
+    
+    dialog.add_button("Quit", ResponseType::Close);
+    
+    // Port conflicts can be fixed on the spot by moving to the next free port
+    let free_port = match error.downcast_ref::<OscError>() {
+        Some(OscError::AddrInUse(_)) => OscManager::find_free_port(bind_address),
+        _ => None,
+    };
+    if let Some(port) = &free_port {
+        dialog.add_button(&format!("Use {}", port), ResponseType::Accept);
+    }
     
+    let app_clone = app.clone();
+    dialog.connect_response(move |dialog, response| {
+        dialog.close();
+        
+        match (&free_port, response) {
+            (Some(new_address), ResponseType::Accept) => {
+                {
+                    let mut config = app_state.config.write();
+                    config.osc.bind_address = new_address.clone();
+                    config.overrides.bind_address = None;
+                    if let Err(e) = config.save() {
+                        app_state.console.write().log_error(&format!("Failed to save config: {}", e));
+                    }
+                }
+                
+                if let Err(e) = start_osc(&app_clone, app_state.clone()) {
+                    eprintln!("Failed to setup application: {:#}", e);
+                    show_startup_error_dialog(&app_clone, &e);
+                }
+            }
+            _ => app_clone.quit(),
+        }
+    });
+    
+    dialog.present();
+}
+
+fn start_app(app: &Application, app_state: Arc<AppState>, osc_manager: Arc<OscManager>) -> Result<()> {
     // Load WASM plugins
     app_state.plugin_loader.write().load_all(
         osc_manager.clone(),
@@ -55,35 +231,98 @@ fn setup_app(app: &Application) -> Result<()> {
     // Start plugins based on their saved enabled state (default: on)
     let mut loader = app_state.plugin_loader.write();
     for plugin in loader.plugins_mut() {
-        let enabled = app_state.config.read()
-            .get_plugin_setting(plugin.info().name.as_str(), "enabled")
-            .map(|v| v != "false")
-            .unwrap_or(true);
+        let enabled = app_state.config.read().plugin_enabled(plugin.name());
         
         if enabled {
             if let Err(e) = plugin.start() {
                 app_state.console.write().log_error(&format!("Failed to start plugin: {}", e));
             }
         } else {
-            app_state.console.write().log_info(&format!("Plugin '{}' is disabled, skipping", plugin.info().name));
+            app_state.console.write().log_info(&format!("Plugin '{}' is disabled, skipping", plugin.name()));
         }
     }
     drop(loader);
     
+    // Optional JSON mirror of OSC traffic for browser overlays
+    let websocket_config = app_state.config.read().websocket.clone();
+    if websocket_config.enabled {
+        if let Err(e) = websocket::start(&websocket_config.bind_address, osc_manager.clone(), app_state.console.clone()) {
+            app_state.console.write().log_error(&format!("Failed to start WebSocket server: {}", e));
+        }
+    }
+    
+    // Optional Prometheus scrape endpoint
+    let metrics_config = app_state.config.read().metrics.clone();
+    if metrics_config.enabled {
+        if let Err(e) = metrics::start(
+            &metrics_config.bind_address,
+            osc_manager.clone(),
+            app_state.plugin_loader.clone(),
+            app_state.console.clone(),
+        ) {
+            app_state.console.write().log_error(&format!("Failed to start metrics server: {}", e));
+        }
+    }
+    
     // Create main window
-    let _main_window = MainWindow::new(app, app_state.clone());
+    let _main_window = MainWindow::new(app, app_state.clone(), osc_manager.clone());
     
-    // Setup plugin update loop (100ms tick)
+    #[cfg(feature = "tray")]
+    osc_app_core::tray::spawn(app_state.console.clone());
+    
+    // Give plugins a chance to persist their state before the process exits
     let app_state_clone = app_state.clone();
-    glib::timeout_add_local(Duration::from_millis(100), move || {
-        let mut loader = app_state_clone.plugin_loader.write();
+    app.connect_shutdown(move |_| {
+        stop_all_plugins(&app_state_clone);
+    });
+    
+    // Setup plugin update loop
+    start_plugin_updates(app_state);
+    
+    Ok(())
+}
+
+fn stop_all_plugins(app_state: &AppState) {
+    let mut loader = app_state.plugin_loader.write();
+    for plugin in loader.plugins_mut() {
+        if plugin.is_running() {
+            if let Err(e) = plugin.stop() {
+                eprintln!("Failed to stop plugin '{}': {}", plugin.name(), e);
+            }
+        }
+    }
+    drop(loader);
+    
+    if let Err(e) = app_state.config.write().flush() {
+        eprintln!("Failed to save config: {}", e);
+    }
+}
+
+// Run plugin_update on a timer. When tick_interval_ms changes in the config the
+// timer replaces itself with one at the new interval.
+fn start_plugin_updates(app_state: Arc<AppState>) {
+    let interval_ms = app_state.config.read().tick_interval_ms.max(MIN_TICK_INTERVAL_MS);
+    
+    glib::timeout_add_local(Duration::from_millis(interval_ms), move || {
+        let mut loader = app_state.plugin_loader.write();
         for plugin in loader.plugins_mut() {
             if let Err(e) = plugin.update() {
-                app_state_clone.console.write().log_error(&format!("Plugin update error: {}", e));
+                app_state.console.write().log_error(&format!("Plugin update error: {}", e));
             }
         }
+        loader.deliver_messages(&app_state.console);
+        drop(loader);
+        
+        // Settings plugins changed through save_config, written once they've settled
+        if let Err(e) = app_state.config.write().flush_if_due() {
+            app_state.console.write().log_error(&format!("Failed to save config: {}", e));
+        }
+        
+        if app_state.config.read().tick_interval_ms.max(MIN_TICK_INTERVAL_MS) != interval_ms {
+            start_plugin_updates(app_state.clone());
+            return glib::ControlFlow::Break;
+        }
+        
         glib::ControlFlow::Continue
     });
-    
-    Ok(())
-}
\ No newline at end of file
+}
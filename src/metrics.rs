@@ -0,0 +1,136 @@
+use anyhow::{Context, Result};
+use parking_lot::RwLock;
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::console::ConsoleLog;
+use crate::osc_manager::OscManager;
+use crate::wasm_loader::WasmPluginLoader;
+
+// A scraper that connects and then stalls shouldn't hold up the next one for long
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Start an HTTP server on `bind_address` that answers `GET /metrics` in the
+/// Prometheus text format.
+///
+/// Exposes the `OscManager::stats` counters plus, for every loaded plugin, whether it
+/// is running, its update timings and its linear memory size. Requests are handled one
+/// at a time on a single thread, which is plenty for a scraper polling every few seconds.
+pub fn start(
+    bind_address: &str,
+    osc_manager: Arc<OscManager>,
+    plugin_loader: Arc<RwLock<WasmPluginLoader>>,
+    console: Arc<RwLock<ConsoleLog>>,
+) -> Result<()> {
+    let listener = TcpListener::bind(bind_address)
+        .with_context(|| format!("Failed to bind metrics server to {}", bind_address))?;
+    console.write().log_info(&format!("Metrics available at http://{}/metrics", bind_address));
+    
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let result = stream
+                .map_err(anyhow::Error::from)
+                .and_then(|stream| serve_request(stream, &osc_manager, &plugin_loader));
+            if let Err(e) = result {
+                console.write().log_error(&format!("Metrics request failed: {}", e));
+            }
+        }
+    });
+    
+    Ok(())
+}
+
+fn serve_request(stream: TcpStream, osc_manager: &OscManager, plugin_loader: &RwLock<WasmPluginLoader>) -> Result<()> {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut reader = BufReader::new(stream);
+    
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Headers aren't used, but have to be read before the response or some clients see a reset
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 0 && !header.trim().is_empty() {
+        header.clear();
+    }
+    
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default();
+    let path = parts.next().unwrap_or_default();
+    // Scrapers sometimes add query parameters; none are supported, so ignore them
+    let path = path.split('?').next().unwrap_or_default();
+    
+    let (status, body) = match (method, path) {
+        ("GET", "/metrics") => ("200 OK", render(osc_manager, plugin_loader)),
+        ("GET", _) => ("404 Not Found", "Not found; try /metrics\n".to_string()),
+        _ => ("405 Method Not Allowed", "Only GET is supported\n".to_string()),
+    };
+    
+    let mut stream = reader.into_inner();
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body,
+    )?;
+    stream.flush()?;
+    Ok(())
+}
+
+fn render(osc_manager: &OscManager, plugin_loader: &RwLock<WasmPluginLoader>) -> String {
+    let stats = osc_manager.stats();
+    let mut out = String::new();
+    
+    metric(&mut out, "foxosc_osc_messages_sent_total", "counter", "OSC messages sent, counting each message in a bundle");
+    let _ = writeln!(out, "foxosc_osc_messages_sent_total {}", stats.messages_sent);
+    metric(&mut out, "foxosc_osc_messages_received_total", "counter", "OSC messages received, counting each message in a bundle");
+    let _ = writeln!(out, "foxosc_osc_messages_received_total {}", stats.messages_received);
+    metric(&mut out, "foxosc_osc_dropped_sends_total", "counter", "OSC sends given up on after retrying or with the retry queue full");
+    let _ = writeln!(out, "foxosc_osc_dropped_sends_total {}", stats.dropped_sends);
+    // Left out entirely until something arrives, rather than reporting a made-up age
+    if let Some(last_receive) = stats.last_receive {
+        metric(&mut out, "foxosc_osc_last_receive_age_seconds", "gauge", "Seconds since the last OSC message was received");
+        let _ = writeln!(out, "foxosc_osc_last_receive_age_seconds {}", last_receive.elapsed().as_secs_f64());
+    }
+    
+    let loader = plugin_loader.read();
+    let plugins = loader.plugins();
+    
+    metric(&mut out, "foxosc_plugin_running", "gauge", "1 if the plugin is running and not paused");
+    for plugin in plugins {
+        let running = plugin.is_running() && !plugin.is_paused();
+        let _ = writeln!(out, "foxosc_plugin_running{{plugin=\"{}\"}} {}", escape_label(plugin.name()), running as u8);
+    }
+    metric(&mut out, "foxosc_plugin_updates_total", "counter", "Calls to plugin_update");
+    for plugin in plugins {
+        let _ = writeln!(out, "foxosc_plugin_updates_total{{plugin=\"{}\"}} {}", escape_label(plugin.name()), plugin.metrics().ticks);
+    }
+    metric(&mut out, "foxosc_plugin_update_seconds_total", "counter", "Time spent in plugin_update");
+    for plugin in plugins {
+        let _ = writeln!(out, "foxosc_plugin_update_seconds_total{{plugin=\"{}\"}} {}", escape_label(plugin.name()), plugin.metrics().total.as_secs_f64());
+    }
+    metric(&mut out, "foxosc_plugin_update_avg_microseconds", "gauge", "Moving average of plugin_update time over roughly the last 20 updates");
+    for plugin in plugins {
+        let _ = writeln!(out, "foxosc_plugin_update_avg_microseconds{{plugin=\"{}\"}} {}", escape_label(plugin.name()), plugin.metrics().avg_us);
+    }
+    metric(&mut out, "foxosc_plugin_memory_bytes", "gauge", "Size of the plugin's WASM linear memory");
+    for plugin in plugins {
+        let _ = writeln!(out, "foxosc_plugin_memory_bytes{{plugin=\"{}\"}} {}", escape_label(plugin.name()), plugin.memory_size());
+    }
+    
+    out
+}
+
+fn metric(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+// Label values are quoted, so backslashes, quotes and newlines need escaping
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
@@ -0,0 +1,157 @@
+use anyhow::Result;
+use midir::{Ignore, MidiInput, MidiInputConnection};
+use parking_lot::{Mutex, RwLock};
+use rosc::OscType;
+use std::sync::Arc;
+
+use crate::config::{Config, MidiEventKind};
+use crate::console::ConsoleLog;
+use crate::osc_manager::OscManager;
+
+const CLIENT_NAME: &str = "fox-osc";
+
+/// Note or CC event decoded from a raw MIDI message
+#[derive(Debug, Clone, Copy)]
+pub struct MidiEvent {
+    pub kind: MidiEventKind,
+    /// 1-16
+    pub channel: u8,
+    pub number: u8,
+    /// 0-127; note off is a note with value 0
+    pub value: u8,
+}
+
+impl MidiEvent {
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        let (status, number, value) = match bytes {
+            [status, number, value, ..] => (*status, *number, *value),
+            _ => return None,
+        };
+        
+        let channel = (status & 0x0F) + 1;
+        let (kind, value) = match status & 0xF0 {
+            0x80 => (MidiEventKind::Note, 0),
+            0x90 => (MidiEventKind::Note, value),
+            0xB0 => (MidiEventKind::ControlChange, value),
+            _ => return None,
+        };
+        
+        Some(Self { kind, channel, number, value })
+    }
+    
+    /// Address plugins can listen on, e.g. `/midi/1/cc/7` or `/midi/10/note/36`
+    pub fn osc_address(&self) -> String {
+        let kind = match self.kind {
+            MidiEventKind::ControlChange => "cc",
+            MidiEventKind::Note => "note",
+        };
+        format!("/midi/{}/{}/{}", self.channel, kind, self.number)
+    }
+    
+    pub fn normalized(&self) -> f32 {
+        self.value as f32 / 127.0
+    }
+}
+
+/// Opens a MIDI input and turns its note and CC events into OSC.
+///
+/// Every event is dispatched locally on `/midi/<channel>/<cc|note>/<number>` so plugins
+/// can listen for it, and events matching a binding in `config.midi.bindings` are also
+/// sent out as a float to the bound address.
+pub struct MidiBridge {
+    osc_manager: Arc<OscManager>,
+    console: Arc<RwLock<ConsoleLog>>,
+    app_config: Arc<RwLock<Config>>,
+    connection: Mutex<Option<(String, MidiInputConnection<()>)>>,
+}
+
+impl MidiBridge {
+    pub fn new(
+        osc_manager: Arc<OscManager>,
+        console: Arc<RwLock<ConsoleLog>>,
+        app_config: Arc<RwLock<Config>>,
+    ) -> Self {
+        Self {
+            osc_manager,
+            console,
+            app_config,
+            connection: Mutex::new(None),
+        }
+    }
+    
+    /// Names of the MIDI input ports currently available
+    pub fn list_ports() -> Result<Vec<String>> {
+        let input = MidiInput::new(CLIENT_NAME).map_err(|e| anyhow::anyhow!("{}", e))?;
+        Ok(input.ports().iter().filter_map(|port| input.port_name(port).ok()).collect())
+    }
+    
+    /// Open the input port called `port_name`, replacing any open connection
+    pub fn connect(&self, port_name: &str) -> Result<()> {
+        self.disconnect();
+        
+        let mut input = MidiInput::new(CLIENT_NAME).map_err(|e| anyhow::anyhow!("{}", e))?;
+        input.ignore(Ignore::All);
+        
+        let port = input.ports()
+            .into_iter()
+            .find(|port| input.port_name(port).ok().as_deref() == Some(port_name))
+            .ok_or_else(|| anyhow::anyhow!("MIDI port '{}' not found", port_name))?;
+        
+        let osc_manager = self.osc_manager.clone();
+        let console = self.console.clone();
+        let app_config = self.app_config.clone();
+        let connection = input
+            .connect(&port, "fox-osc-input", move |_, bytes, _| {
+                if let Some(event) = MidiEvent::parse(bytes) {
+                    Self::handle_event(&event, &osc_manager, &console, &app_config);
+                }
+            }, ())
+            .map_err(|e| anyhow::anyhow!("Failed to open MIDI port '{}': {}", port_name, e))?;
+        
+        *self.connection.lock() = Some((port_name.to_string(), connection));
+        self.console.write().log_info(&format!("MIDI input connected: {}", port_name));
+        
+        Ok(())
+    }
+    
+    pub fn disconnect(&self) {
+        if let Some((port_name, connection)) = self.connection.lock().take() {
+            connection.close();
+            self.console.write().log_info(&format!("MIDI input disconnected: {}", port_name));
+        }
+    }
+    
+    /// Name of the port that is currently open, if any
+    pub fn connected_port(&self) -> Option<String> {
+        self.connection.lock().as_ref().map(|(name, _)| name.clone())
+    }
+    
+    // Runs on the MIDI backend's thread
+    fn handle_event(
+        event: &MidiEvent,
+        osc_manager: &OscManager,
+        console: &RwLock<ConsoleLog>,
+        app_config: &RwLock<Config>,
+    ) {
+        osc_manager.dispatch_local(&event.osc_address(), vec![OscType::Float(event.normalized())]);
+        
+        let addresses: Vec<String> = app_config.read().midi.bindings
+            .iter()
+            .filter(|b| b.kind == event.kind && b.number == event.number)
+            .filter(|b| b.channel.is_none_or(|channel| channel == event.channel))
+            .map(|b| b.address.clone())
+            .collect();
+        
+        for address in addresses {
+            if let Err(e) = osc_manager.send_float(&address, event.normalized()) {
+                console.write().log_error(&format!("MIDI to OSC send failed: {}", e));
+            }
+        }
+    }
+}
+
+impl Drop for MidiBridge {
+    fn drop(&mut self) {
+        self.disconnect();
+    }
+}
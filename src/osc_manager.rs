@@ -1,93 +1,553 @@
-use anyhow::Result;
-use rosc::{OscMessage, OscPacket, OscType};
-use std::net::UdpSocket;
+use anyhow::{Context, Result};
+use rosc::{OscBundle, OscMessage, OscPacket, OscTime, OscType};
+use std::net::{SocketAddr, SocketAddrV6, ToSocketAddrs, UdpSocket};
+use socket2::{Domain, Protocol, Socket, Type};
 use std::sync::Arc;
-use parking_lot::RwLock;
-use std::collections::HashMap;
-use std::thread;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use parking_lot::{Condvar, MutexGuard, RwLock, Mutex};
+use std::cmp::{Ordering as CmpOrdering, Reverse};
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use crate::console::ConsoleLog;
+use crate::config::{OscConfig, PacketDump};
+use crate::console::{format_osc_args, format_osc_value, ConsoleLog};
+
+const RECEIVE_TIMEOUT: Duration = Duration::from_millis(100);
+
+// Future-dated bundles held for later dispatch before new ones are dropped
+const MAX_SCHEDULED_PACKETS: usize = 1024;
+
+// Seconds between the OSC/NTP epoch (1900) and the Unix epoch
+const OSC_UNIX_OFFSET: u64 = 2_208_988_800;
+
+// Failed sends waiting for a retry before new failures are dropped
+const MAX_RETRY_QUEUE: usize = 256;
+// Retries per failed send; the delay before each doubles from RETRY_BASE_DELAY
+const MAX_SEND_RETRIES: u32 = 3;
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(100);
+
+// Floats closer than this to the last sent value count as unchanged
+const FLOAT_CHANGE_EPSILON: f32 = 1e-4;
 
 type MessageCallback = Arc<dyn Fn(&str, &OscType) + Send + Sync>;
+type ListenerMap = HashMap<String, Vec<(ListenerId, MessageCallback)>>;
+// Sees every received message, whether or not anything listens on its address
+type MessageObserver = Arc<dyn Fn(&str, &[OscType]) + Send + Sync>;
+// Latest arguments received on each address
+type ValueCache = HashMap<String, Vec<OscType>>;
 
-pub struct OscManager {
+/// Opaque handle returned by `register_listener`, used to remove that one callback
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ListenerId(u64);
+
+#[derive(Debug, thiserror::Error)]
+pub enum OscError {
+    #[error("chatbox rate limited, next send allowed in {0:?}")]
+    ChatboxRateLimited(Duration),
+    #[error("OSC port {0} is already in use by another application. Close that application or change `osc.bind_address` in config.toml")]
+    AddrInUse(String),
+}
+
+/// Contents of a future-dated bundle waiting for its time tag
+struct ScheduledPacket {
+    due: SystemTime,
+    // Keeps bundles with the same time tag in arrival order
+    seq: u64,
+    packet: OscPacket,
+}
+
+impl PartialEq for ScheduledPacket {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == CmpOrdering::Equal
+    }
+}
+
+impl Eq for ScheduledPacket {}
+
+impl PartialOrd for ScheduledPacket {
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScheduledPacket {
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        (self.due, self.seq).cmp(&(other.due, other.seq))
+    }
+}
+
+/// Min-heap of packets from future-dated bundles, drained by the receive loop
+#[derive(Default)]
+struct Scheduler {
+    pending: BinaryHeap<Reverse<ScheduledPacket>>,
+    next_seq: u64,
+}
+
+impl Scheduler {
+    /// Queue `packet` for `due`. Returns false if the queue is full.
+    fn schedule(&mut self, due: SystemTime, packet: OscPacket) -> bool {
+        if self.pending.len() >= MAX_SCHEDULED_PACKETS {
+            return false;
+        }
+        self.pending.push(Reverse(ScheduledPacket { due, seq: self.next_seq, packet }));
+        self.next_seq += 1;
+        true
+    }
+    
+    /// Take the earliest packet if its time has come
+    fn pop_due(&mut self, now: SystemTime) -> Option<OscPacket> {
+        if self.pending.peek()?.0.due > now {
+            return None;
+        }
+        self.pending.pop().map(|Reverse(scheduled)| scheduled.packet)
+    }
+    
+    /// How long until the earliest packet is due, if any are queued
+    fn time_until_next(&self, now: SystemTime) -> Option<Duration> {
+        self.pending.peek().map(|Reverse(next)| next.due.duration_since(now).unwrap_or_default())
+    }
+}
+
+/// Traffic counters since startup, from `OscManager::stats`
+#[derive(Debug, Clone, Default)]
+pub struct OscStats {
+    pub messages_sent: u64,
+    pub messages_received: u64,
+    /// Sends given up on; see `OscManager::dropped_sends`
+    pub dropped_sends: u64,
+    pub last_send_error: Option<String>,
+    pub last_receive: Option<Instant>,
+}
+
+/// Live counters behind `OscStats`, shared with the receiver and retry threads
+#[derive(Default)]
+struct Traffic {
+    sent: AtomicU64,
+    received: AtomicU64,
+    last_send_error: Mutex<Option<String>>,
+    last_receive: Mutex<Option<Instant>>,
+}
+
+impl Traffic {
+    fn send_failed(&self, target: SocketAddr, error: &std::io::Error) {
+        *self.last_send_error.lock() = Some(format!("{}: {}", target, error));
+    }
+}
+
+/// What the receiver thread shares with the manager, besides its socket
+struct ReceiveContext {
+    listeners: Arc<RwLock<ListenerMap>>,
+    values: Arc<RwLock<ValueCache>>,
+    observers: Arc<RwLock<Vec<MessageObserver>>>,
+    console: Arc<RwLock<ConsoleLog>>,
+    traffic: Arc<Traffic>,
+    relay_address: Option<SocketAddr>,
+    packet_dump: PacketDump,
+}
+
+/// An encoded packet that couldn't be sent to one target
+struct FailedSend {
     socket: Arc<UdpSocket>,
-    target_address: String,
+    target: SocketAddr,
+    buf: Vec<u8>,
+    retries: u32,
+    next_attempt: Instant,
+}
+
+/// Failed sends shared with the retry thread, which wakes when one is added or due
+#[derive(Default)]
+struct RetryQueue {
+    pending: Mutex<VecDeque<FailedSend>>,
+    wake: Condvar,
+    shutdown: AtomicBool,
+    dropped: AtomicU64,
+}
+
+impl RetryQueue {
+    /// Queue `send` for another try. Returns false, counting it as dropped, if the queue is full.
+    fn push(&self, send: FailedSend) -> bool {
+        let mut pending = self.pending.lock();
+        if pending.len() >= MAX_RETRY_QUEUE {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+        pending.push_back(send);
+        self.wake.notify_one();
+        true
+    }
+}
+
+/// Running receiver thread plus the flag that tells it to exit
+struct Receiver {
+    shutdown: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+}
+
+pub struct OscManager {
+    socket: RwLock<Arc<UdpSocket>>,
+    bind_address: RwLock<String>,
+    reuse_address: bool,
+    target_addresses: RwLock<Vec<SocketAddr>>,
+    relay_address: Option<SocketAddr>,
+    packet_dump: PacketDump,
     console: Arc<RwLock<ConsoleLog>>,
-    listeners: Arc<RwLock<HashMap<String, Vec<MessageCallback>>>>,
+    listeners: Arc<RwLock<ListenerMap>>,
+    values: Arc<RwLock<ValueCache>>,
+    observers: Arc<RwLock<Vec<MessageObserver>>>,
+    next_listener_id: AtomicU64,
+    // None when `receive_enabled` is off
+    receiver: Mutex<Option<Receiver>>,
+    receive_enabled: bool,
+    chatbox_interval: Duration,
+    last_chatbox_send: Mutex<Option<Instant>>,
+    batch: Mutex<Option<Vec<OscPacket>>>,
+    retries: Arc<RetryQueue>,
+    traffic: Arc<Traffic>,
+    retry_thread: Mutex<Option<JoinHandle<()>>>,
+    send_only_on_change: bool,
+    last_sent_floats: Mutex<HashMap<String, f32>>,
 }
 
 impl OscManager {
-    pub fn new(bind_address: &str, target_address: &str, console: Arc<RwLock<ConsoleLog>>) -> Result<Self> {
-        let socket = UdpSocket::bind(bind_address)?;
-        socket.set_nonblocking(true)?;
+    pub fn new(osc_config: &OscConfig, console: Arc<RwLock<ConsoleLog>>) -> Result<Self> {
+        let bind_address = osc_config.bind_address.as_str();
+        let target_addresses = Self::parse_targets(&osc_config.target_address)?;
+        
+        let socket = if osc_config.enable_receive {
+            let socket = Self::bind_socket(bind_address, osc_config.reuse_address)?;
+            console.write().log_info(&format!("OSC bound to {}", bind_address));
+            socket
+        } else {
+            // Any free port will do for sending; the IPv6 wildcard is dual-stack, so only
+            // use it when a target needs it in case IPv6 is disabled on this machine
+            let any_address = if target_addresses.iter().any(SocketAddr::is_ipv6) { "[::]:0" } else { "0.0.0.0:0" };
+            let socket = Self::bind_socket(any_address, false)?;
+            console.write().log_info("OSC receive disabled, sending only");
+            socket
+        };
         let socket = Arc::new(socket);
         
-        console.write().log_info(&format!("OSC bound to {}", bind_address));
-        console.write().log_info(&format!("OSC target: {}", target_address));
+        console.write().log_info(&format!("OSC target: {}", Self::format_targets(&target_addresses)));
         
         let listeners = Arc::new(RwLock::new(HashMap::new()));
+        let values = Arc::new(RwLock::new(HashMap::new()));
+        let observers = Arc::new(RwLock::new(Vec::new()));
         
         // Start receiver thread
-        let socket_clone = socket.clone();
-        let listeners_clone = listeners.clone();
-        let console_clone = console.clone();
+        let relay_address = match osc_config.relay_address.as_deref().map(str::trim) {
+            Some(relay) if !relay.is_empty() => Some(resolve_address(relay)?),
+            _ => None,
+        };
+        if let Some(relay) = &relay_address {
+            console.write().log_info(&format!("OSC relay: {}", relay));
+        }
         
-        thread::spawn(move || {
-            Self::receive_loop(socket_clone, listeners_clone, console_clone);
-        });
+        let traffic = Arc::new(Traffic::default());
+        let receiver = osc_config.enable_receive.then(|| Self::spawn_receiver(socket.clone(), ReceiveContext {
+            listeners: listeners.clone(),
+            values: values.clone(),
+            observers: observers.clone(),
+            console: console.clone(),
+            traffic: traffic.clone(),
+            relay_address,
+            packet_dump: osc_config.packet_dump,
+        }));
+        
+        let retries = Arc::new(RetryQueue::default());
+        let retries_clone = retries.clone();
+        let traffic_clone = traffic.clone();
+        let console_clone = console.clone();
+        let retry_thread = thread::spawn(move || Self::retry_loop(&retries_clone, &traffic_clone, &console_clone));
         
         Ok(Self {
-            socket,
-            target_address: target_address.to_string(),
+            socket: RwLock::new(socket),
+            bind_address: RwLock::new(bind_address.to_string()),
+            reuse_address: osc_config.reuse_address,
+            target_addresses: RwLock::new(target_addresses),
+            relay_address,
+            packet_dump: osc_config.packet_dump,
             console,
             listeners,
+            values,
+            observers,
+            next_listener_id: AtomicU64::new(0),
+            receiver: Mutex::new(receiver),
+            receive_enabled: osc_config.enable_receive,
+            chatbox_interval: Duration::from_millis(osc_config.chatbox_interval_ms),
+            last_chatbox_send: Mutex::new(None),
+            batch: Mutex::new(None),
+            retries,
+            traffic,
+            retry_thread: Mutex::new(Some(retry_thread)),
+            send_only_on_change: osc_config.send_only_on_change,
+            last_sent_floats: Mutex::new(HashMap::new()),
         })
     }
     
-    fn receive_loop(
-        socket: Arc<UdpSocket>,
-        listeners: Arc<RwLock<HashMap<String, Vec<MessageCallback>>>>,
-        console: Arc<RwLock<ConsoleLog>>,
-    ) {
+    // Creates the socket in the right domain for the address, so "[::]:9001" gives an
+    // IPv6 socket. IPv6 sockets are dual-stack so IPv4 targets keep working.
+    fn bind_socket(bind_address: &str, reuse_address: bool) -> Result<UdpSocket> {
+        let addr = resolve_address(bind_address)?;
+        
+        let socket = Socket::new(Domain::for_address(addr), Type::DGRAM, Some(Protocol::UDP))?;
+        if addr.is_ipv6() {
+            socket.set_only_v6(false)?;
+        }
+        socket.set_reuse_address(reuse_address)?;
+        
+        socket.bind(&addr.into()).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::AddrInUse {
+                OscError::AddrInUse(bind_address.to_string()).into()
+            } else {
+                anyhow::anyhow!("Failed to bind OSC socket to {}: {}", bind_address, e)
+            }
+        })?;
+        
+        let socket: UdpSocket = socket.into();
+        // Blocking reads wake immediately on data; the timeout only bounds how long
+        // the receiver takes to notice a shutdown request
+        socket.set_read_timeout(Some(RECEIVE_TIMEOUT))?;
+        Ok(socket)
+    }
+    
+    /// Find the next bindable port after the one in `bind_address`, checking up to 20 ports
+    pub fn find_free_port(bind_address: &str) -> Option<String> {
+        let addr = resolve_address(bind_address).ok()?;
+        
+        (1..=20)
+            .filter_map(|offset| addr.port().checked_add(offset))
+            .map(|port| SocketAddr::new(addr.ip(), port))
+            .find(|candidate| UdpSocket::bind(candidate).is_ok())
+            .map(|candidate| candidate.to_string())
+    }
+    
+    fn spawn_receiver(socket: Arc<UdpSocket>, context: ReceiveContext) -> Receiver {
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let shutdown_clone = shutdown.clone();
+        
+        let thread = thread::spawn(move || {
+            Self::receive_loop(socket, context, shutdown_clone);
+        });
+        
+        Receiver { shutdown, thread }
+    }
+    
+    /// Stop the receiver thread and wait for it to exit. Safe to call more than once.
+    pub fn shutdown(&self) {
+        if let Some(receiver) = self.receiver.lock().take() {
+            receiver.shutdown.store(true, Ordering::Relaxed);
+            if receiver.thread.join().is_err() {
+                self.console.write().log_error("OSC receiver thread panicked");
+            }
+        }
+    }
+    
+    /// Move the listen socket to a new address and restart the receiver on it.
+    /// The old socket is closed first, so moving to another IP on the same port works
+    /// without `reuse_address`. If the new address can't be bound the old one is bound
+    /// again and the error returned. With receiving disabled this only records the
+    /// address; nothing is bound.
+    pub fn rebind(&self, bind_address: &str) -> Result<()> {
+        if *self.bind_address.read() == bind_address {
+            return Ok(());
+        }
+        resolve_address(bind_address)?;
+        if !self.receive_enabled {
+            *self.bind_address.write() = bind_address.to_string();
+            return Ok(());
+        }
+        
+        // Release the port: stop the receiver and swap in a throwaway socket for sends,
+        // including queued retries, which would otherwise keep the old socket open
+        let old_address = self.bind_address.read().clone();
+        self.shutdown();
+        let temporary = Arc::new(Self::bind_socket(Self::any_address(&old_address), false)?);
+        let old_socket = std::mem::replace(&mut *self.socket.write(), temporary.clone());
+        for send in self.retries.pending.lock().iter_mut().filter(|send| Arc::ptr_eq(&send.socket, &old_socket)) {
+            send.socket = temporary.clone();
+        }
+        drop(old_socket);
+        
+        let error = match Self::bind_socket(bind_address, self.reuse_address) {
+            Ok(socket) => {
+                self.start_receiver(Arc::new(socket), bind_address);
+                self.console.write().log_info(&format!("OSC rebound to {}", bind_address));
+                return Ok(());
+            }
+            Err(e) => e,
+        };
+        
+        match Self::bind_socket(&old_address, self.reuse_address) {
+            Ok(socket) => self.start_receiver(Arc::new(socket), &old_address),
+            Err(e) => self.console.write().log_error(&format!(
+                "Failed to bind OSC back to {}, receiving stopped: {}", old_address, e
+            )),
+        }
+        Err(error)
+    }
+    
+    // Unspecified address of the same family, port 0, for a socket that only sends
+    fn any_address(address: &str) -> &'static str {
+        match resolve_address(address) {
+            Ok(addr) if addr.is_ipv6() => "[::]:0",
+            _ => "0.0.0.0:0",
+        }
+    }
+    
+    fn start_receiver(&self, socket: Arc<UdpSocket>, bind_address: &str) {
+        *self.socket.write() = socket.clone();
+        *self.bind_address.write() = bind_address.to_string();
+        *self.receiver.lock() = Some(Self::spawn_receiver(socket, ReceiveContext {
+            listeners: self.listeners.clone(),
+            values: self.values.clone(),
+            observers: self.observers.clone(),
+            console: self.console.clone(),
+            traffic: self.traffic.clone(),
+            relay_address: self.relay_address,
+            packet_dump: self.packet_dump,
+        }));
+    }
+    
+    /// Check `target_address` the way `set_target` does, without applying it
+    pub fn validate_target(target_address: &str) -> Result<()> {
+        Self::parse_targets(target_address).map(|_| ())
+    }
+    
+    pub fn set_target(&self, target_address: &str) -> Result<()> {
+        let targets = Self::parse_targets(target_address)?;
+        
+        self.console.write().log_info(&format!("OSC target: {}", Self::format_targets(&targets)));
+        *self.target_addresses.write() = targets;
+        Ok(())
+    }
+    
+    // target_address may hold several comma-separated destinations to fan out to
+    fn parse_targets(target_address: &str) -> Result<Vec<SocketAddr>> {
+        let targets = target_address
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(resolve_address)
+            .collect::<Result<Vec<_>>>()?;
+        
+        if targets.is_empty() {
+            anyhow::bail!("No OSC target address given");
+        }
+        Ok(targets)
+    }
+    
+    fn format_targets(targets: &[SocketAddr]) -> String {
+        targets.iter().map(|t| t.to_string()).collect::<Vec<_>>().join(", ")
+    }
+    
+    fn receive_loop(socket: Arc<UdpSocket>, context: ReceiveContext, shutdown: Arc<AtomicBool>) {
+        let ReceiveContext { listeners, values, observers, console, traffic, relay_address, packet_dump } = context;
         let mut buf = [0u8; rosc::decoder::MTU];
+        let local_addr = socket.local_addr().ok();
+        let mut scheduler = Scheduler::default();
+        let mut read_timeout = RECEIVE_TIMEOUT;
         
-        loop {
+        while !shutdown.load(Ordering::Relaxed) {
+            let now = SystemTime::now();
+            while let Some(packet) = scheduler.pop_due(now) {
+                Self::handle_packet(packet, &mut scheduler, &listeners, &values, &observers, &console);
+            }
+            
+            // Wake up in time for the next scheduled bundle; a zero timeout isn't allowed
+            let timeout = scheduler.time_until_next(now)
+                .map_or(RECEIVE_TIMEOUT, |wait| wait.clamp(Duration::from_millis(1), RECEIVE_TIMEOUT));
+            if timeout != read_timeout {
+                if let Err(e) = socket.set_read_timeout(Some(timeout)) {
+                    console.write().log_error(&format!("Failed to set OSC read timeout: {}", e));
+                }
+                read_timeout = timeout;
+            }
+            
             match socket.recv_from(&mut buf) {
-                Ok((size, _addr)) => {
+                Ok((size, source)) => {
                     let packet = match rosc::decoder::decode_udp(&buf[..size]) {
                         Ok((_, packet)) => packet,
-                        Err(e) => {
+                        Err(e) if packet_dump == PacketDump::Off => {
                             console.write().log_error(&format!("Failed to decode OSC packet: {}", e));
                             continue;
                         }
+                        Err(e) => {
+                            console.write().log_error(&format!(
+                                "Failed to decode OSC packet from {} ({} bytes): {}\n{}", source, size, e, hex_dump(&buf[..size])
+                            ));
+                            continue;
+                        }
                     };
+                    if packet_dump == PacketDump::All {
+                        console.write().log_info(&format!("OSC packet from {} ({} bytes):\n{}", source, size, hex_dump(&buf[..size])));
+                    }
+                    
+                    traffic.received.fetch_add(message_count(&packet), Ordering::Relaxed);
+                    *traffic.last_receive.lock() = Some(Instant::now());
                     
-                    Self::handle_packet(packet, &listeners, &console);
+                    Self::handle_packet(packet, &mut scheduler, &listeners, &values, &observers, &console);
+                    
+                    // Pass the original bytes through untouched, unless we sent them ourselves
+                    if let Some(relay) = relay_address {
+                        if !Self::is_own_packet(source, local_addr) {
+                            if let Err(e) = socket.send_to(&buf[..size], map_to_socket_family(relay, local_addr)) {
+                                console.write().log_error(&format!("OSC relay to {} failed: {}", relay, e));
+                            }
+                        }
+                    }
                 }
-                Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
-                    // No data available, sleep briefly
-                    thread::sleep(std::time::Duration::from_millis(10));
+                Err(ref e) if matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) => {
+                    // Read timed out with no data, loop to re-check shutdown
                 }
                 Err(e) => {
                     console.write().log_error(&format!("OSC receive error: {}", e));
                 }
             }
         }
+        
+        // Restore the default for a socket that's reused after a rebind failure
+        if read_timeout != RECEIVE_TIMEOUT {
+            let _ = socket.set_read_timeout(Some(RECEIVE_TIMEOUT));
+        }
+    }
+    
+    fn is_own_packet(source: SocketAddr, local_addr: Option<SocketAddr>) -> bool {
+        match local_addr {
+            Some(local) => {
+                source.port() == local.port()
+                    && (source.ip() == local.ip() || (local.ip().is_unspecified() && source.ip().to_canonical().is_loopback()))
+            }
+            None => false,
+        }
     }
     
+    // Bundles time-tagged in the future are queued on `scheduler` and dispatched by the
+    // receive loop when due; immediate or past-dated bundles dispatch right away
     fn handle_packet(
         packet: OscPacket,
-        listeners: &Arc<RwLock<HashMap<String, Vec<MessageCallback>>>>,
+        scheduler: &mut Scheduler,
+        listeners: &Arc<RwLock<ListenerMap>>,
+        values: &Arc<RwLock<ValueCache>>,
+        observers: &Arc<RwLock<Vec<MessageObserver>>>,
         console: &Arc<RwLock<ConsoleLog>>,
     ) {
         match packet {
             OscPacket::Message(msg) => {
-                Self::handle_message(msg, listeners, console);
+                Self::handle_message(msg, listeners, values, observers, console);
             }
             OscPacket::Bundle(bundle) => {
+                if let Some(due) = osc_time_to_system_time(bundle.timetag) {
+                    if due > SystemTime::now() {
+                        if !scheduler.schedule(due, OscPacket::Bundle(OscBundle { timetag: IMMEDIATELY, content: bundle.content })) {
+                            console.write().log_error("Too many scheduled OSC bundles pending, dropping bundle");
+                        }
+                        return;
+                    }
+                }
+                
                 for packet in bundle.content {
-                    Self::handle_packet(packet, listeners, console);
+                    Self::handle_packet(packet, scheduler, listeners, values, observers, console);
                 }
             }
         }
@@ -95,40 +555,124 @@ impl OscManager {
     
     fn handle_message(
         msg: OscMessage,
-        listeners: &Arc<RwLock<HashMap<String, Vec<MessageCallback>>>>,
+        listeners: &Arc<RwLock<ListenerMap>>,
+        values: &Arc<RwLock<ValueCache>>,
+        observers: &Arc<RwLock<Vec<MessageObserver>>>,
         console: &Arc<RwLock<ConsoleLog>>,
     ) {
-        let listeners_read = listeners.read();
+        values.write().insert(msg.addr.clone(), msg.args.clone());
+        
+        for observer in observers.read().iter() {
+            observer(&msg.addr, &msg.args);
+        }
         
-        if let Some(callbacks) = listeners_read.get(&msg.addr) {
+        let callbacks: Vec<MessageCallback> = {
+            let listeners_read = listeners.read();
+            
+            // Exact listeners win; wildcard listeners like /avatar/parameters/* are only
+            // scanned for when nothing listens on the address itself
+            match listeners_read.get(&msg.addr) {
+                Some(entries) => entries.iter().map(|(_, cb)| cb.clone()).collect(),
+                None => listeners_read.iter()
+                    .filter(|(pattern, _)| is_osc_pattern(pattern) && osc_pattern_matches(pattern, &msg.addr))
+                    .flat_map(|(_, entries)| entries.iter().map(|(_, cb)| cb.clone()))
+                    .collect(),
+            }
+        };
+        
+        if !callbacks.is_empty() {
             // This address has listeners - log it AND call callbacks
             for arg in &msg.args {
-                for callback in callbacks {
+                for callback in &callbacks {
                     callback(&msg.addr, arg);
                 }
             }
             
             // Log to console (shows in Log tab because plugin is using it)
-            let value_str = format!("{:?}", msg.args);
-            console.write().log_osc_received(&msg.addr, &value_str);
-        } else {
-            // No listeners - only update active addresses (shows in Active Addresses tab only)
-            let value_str = format!("{:?}", msg.args);
-            console.write().update_active_address(&msg.addr, &value_str);
+            console.write().log_osc_received(&msg.addr, &msg.args);
+        } else if console.read().is_enabled() {
+            // No listeners - only update active addresses (shows in Active Addresses tab only).
+            // Skipped with the console off, so the bulk of head-tracking traffic costs no
+            // more than the value cache update and observers above.
+            console.write().update_active_address(&msg.addr, &msg.args);
         }
     }
     
-    pub fn register_listener<F>(&self, address: String, callback: F)
+    /// Feed a message from a local source (e.g. MIDI) through the same path as received
+    /// packets, so listeners, the value cache and the console all see it
+    pub fn dispatch_local(&self, address: &str, args: Vec<OscType>) {
+        let msg = OscMessage { addr: address.to_string(), args };
+        Self::handle_message(msg, &self.listeners, &self.values, &self.observers, &self.console);
+    }
+    
+    /// Latest arguments received on `address`, if anything was ever received there
+    pub fn get_value(&self, address: &str) -> Option<Vec<OscType>> {
+        self.values.read().get(address).cloned()
+    }
+    
+    /// Latest value on `address` as a float. Ints and bools convert (bools to 0.0/1.0).
+    /// Longs outside the i32 range give None, as they're rejected on the way to plugins.
+    pub fn get_float(&self, address: &str) -> Option<f32> {
+        let values = self.values.read();
+        match values.get(address)?.first()? {
+            OscType::Float(f) => Some(*f),
+            OscType::Double(d) => Some(*d as f32),
+            OscType::Int(i) => Some(*i as f32),
+            OscType::Long(l) => i32::try_from(*l).ok().map(|i| i as f32),
+            OscType::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+            _ => None,
+        }
+    }
+    
+    /// Whether incoming OSC is being received; false when `osc.enable_receive` is off
+    pub fn is_receiving(&self) -> bool {
+        self.receive_enabled
+    }
+    
+    /// Call `callback` with each argument of messages on `address`. When
+    /// `osc.enable_receive` is off this is effectively a no-op: the listener is kept
+    /// and still sees `dispatch_local` messages, but nothing arrives from the network.
+    pub fn register_listener<F>(&self, address: String, callback: F) -> ListenerId
     where
         F: Fn(&str, &OscType) + Send + Sync + 'static,
     {
+        let id = ListenerId(self.next_listener_id.fetch_add(1, Ordering::Relaxed));
+        
         let mut listeners = self.listeners.write();
         listeners
             .entry(address.clone())
             .or_insert_with(Vec::new)
-            .push(Arc::new(callback));
+            .push((id, Arc::new(callback)));
         
         self.console.write().log_info(&format!("Registered OSC listener for: {}", address));
+        id
+    }
+    
+    /// Call `observer` with every message received from now on, including ones with no
+    /// listeners. Unlike listeners, observers don't make the address show in the Log tab.
+    pub fn add_observer<F>(&self, observer: F)
+    where
+        F: Fn(&str, &[OscType]) + Send + Sync + 'static,
+    {
+        self.observers.write().push(Arc::new(observer));
+    }
+    
+    // Removes just this callback, leaving other listeners on the same address intact
+    pub fn unregister_listener(&self, id: ListenerId) {
+        let mut listeners = self.listeners.write();
+        let address = listeners.iter_mut().find_map(|(address, entries)| {
+            let before = entries.len();
+            entries.retain(|(entry_id, _)| *entry_id != id);
+            (entries.len() != before).then(|| address.clone())
+        });
+        
+        if let Some(address) = address {
+            if listeners.get(&address).is_some_and(|entries| entries.is_empty()) {
+                listeners.remove(&address);
+            }
+            drop(listeners);
+            self.console.write().log_info(&format!("Unregistered OSC listener for: {}", address));
+        }
     }
     
     pub fn unregister_all_listeners(&self, address: &str) {
@@ -138,16 +682,233 @@ impl OscManager {
         self.console.write().log_info(&format!("Unregistered OSC listeners for: {}", address));
     }
     
+    /// Collect sends into one bundle until `end_batch`, so e.g. an RGB color goes out as
+    /// one UDP packet instead of three. Nested calls join the open batch.
+    pub fn begin_batch(&self) {
+        let mut batch = self.batch.lock();
+        if batch.is_none() {
+            *batch = Some(Vec::new());
+        }
+    }
+    
+    /// Send everything collected since `begin_batch` as a single bundle
+    pub fn end_batch(&self) -> Result<()> {
+        let packets = match self.batch.lock().take() {
+            Some(packets) => packets,
+            None => return Ok(()),
+        };
+        
+        match packets.len() {
+            0 => Ok(()),
+            1 => self.send_now(&packets[0]),
+            _ => self.send_now(&OscPacket::Bundle(OscBundle {
+                timetag: IMMEDIATELY,
+                content: packets,
+            })),
+        }
+    }
+    
+    // Encode once and send to every target, reporting the last failure if any
+    fn send_packet(&self, packet: &OscPacket) -> Result<()> {
+        if let Some(batch) = self.batch.lock().as_mut() {
+            batch.push(packet.clone());
+            return Ok(());
+        }
+        
+        self.send_now(packet)
+    }
+    
+    // A send that fails, e.g. because the network isn't up yet at startup, is queued for
+    // the retry thread and counts as sent. Only a full retry queue is reported here.
+    // A retried packet can arrive after ones sent later.
+    fn send_now(&self, packet: &OscPacket) -> Result<()> {
+        let buf = rosc::encoder::encode(packet)?;
+        self.send_encoded(buf, message_count(packet))
+    }
+    
+    // `buf` holds `messages` OSC messages, for the sent counter
+    fn send_encoded(&self, buf: Vec<u8>, messages: u64) -> Result<()> {
+        let socket = self.socket.read().clone();
+        let local_addr = socket.local_addr().ok();
+        
+        self.traffic.sent.fetch_add(messages, Ordering::Relaxed);
+        let mut result = Ok(());
+        for target in self.target_addresses.read().iter() {
+            let target = map_to_socket_family(*target, local_addr);
+            if let Err(e) = socket.send_to(&buf, target) {
+                self.traffic.send_failed(target, &e);
+                let queued = self.retries.push(FailedSend {
+                    socket: socket.clone(),
+                    target,
+                    buf: buf.clone(),
+                    retries: 0,
+                    next_attempt: Instant::now() + RETRY_BASE_DELAY,
+                });
+                if !queued {
+                    result = Err(anyhow::anyhow!("Failed to send to {}: {} (retry queue full)", target, e));
+                }
+            }
+        }
+        result
+    }
+    
+    /// Sends given up on: failed every retry, or failed while the retry queue was full
+    pub fn dropped_sends(&self) -> u64 {
+        self.retries.dropped.load(Ordering::Relaxed)
+    }
+    
+    /// Message counts and recent activity, for telling whether OSC is actually flowing.
+    /// Messages inside bundles count individually; `dispatch_local` messages don't count.
+    pub fn stats(&self) -> OscStats {
+        OscStats {
+            messages_sent: self.traffic.sent.load(Ordering::Relaxed),
+            messages_received: self.traffic.received.load(Ordering::Relaxed),
+            dropped_sends: self.dropped_sends(),
+            last_send_error: self.traffic.last_send_error.lock().clone(),
+            last_receive: *self.traffic.last_receive.lock(),
+        }
+    }
+    
+    // Runs on its own thread until `retries.shutdown` is set, resending failed packets
+    // as they come due and dropping them after MAX_SEND_RETRIES
+    fn retry_loop(retries: &RetryQueue, traffic: &Traffic, console: &RwLock<ConsoleLog>) {
+        let mut pending = retries.pending.lock();
+        while !retries.shutdown.load(Ordering::Relaxed) {
+            let now = Instant::now();
+            let (due, waiting): (VecDeque<_>, VecDeque<_>) = pending.drain(..).partition(|send| send.next_attempt <= now);
+            *pending = waiting;
+            
+            if due.is_empty() {
+                match pending.iter().map(|send| send.next_attempt).min() {
+                    Some(next) => {
+                        retries.wake.wait_until(&mut pending, next);
+                    }
+                    None => retries.wake.wait(&mut pending),
+                }
+                continue;
+            }
+            
+            // Send without holding the lock, so senders never wait on the network
+            let still_failing = MutexGuard::unlocked(&mut pending, || {
+                let mut still_failing = Vec::new();
+                for mut send in due {
+                    let error = match send.socket.send_to(&send.buf, send.target) {
+                        Ok(_) => continue,
+                        Err(e) => e,
+                    };
+                    traffic.send_failed(send.target, &error);
+                    send.retries += 1;
+                    if send.retries >= MAX_SEND_RETRIES {
+                        retries.dropped.fetch_add(1, Ordering::Relaxed);
+                        console.write().log_error(&format!(
+                            "Dropped OSC packet to {} after {} retries: {}", send.target, MAX_SEND_RETRIES, error
+                        ));
+                        continue;
+                    }
+                    send.next_attempt = Instant::now() + RETRY_BASE_DELAY * 2u32.pow(send.retries);
+                    still_failing.push(send);
+                }
+                still_failing
+            });
+            pending.extend(still_failing);
+        }
+    }
+    
+    /// Send `messages` together as one bundle, so the receiver applies them in the same
+    /// frame. Nothing is sent unless the whole bundle encodes. The send-only-on-change
+    /// filter doesn't apply, since dropping part of the bundle would defeat the point.
+    pub fn send_bundle(&self, messages: Vec<(String, OscType)>) -> Result<()> {
+        if messages.is_empty() {
+            return Ok(());
+        }
+        
+        let logged: Vec<(String, String)> = messages
+            .iter()
+            .map(|(addr, arg)| (addr.clone(), format_osc_value(arg)))
+            .collect();
+        let content = messages
+            .into_iter()
+            .map(|(addr, arg)| OscPacket::Message(OscMessage { addr, args: vec![arg] }))
+            .collect();
+        let bundle = OscPacket::Bundle(OscBundle { timetag: IMMEDIATELY, content });
+        
+        // Encode up front so a bad bundle fails here even when it would join an open batch
+        rosc::encoder::encode(&bundle)?;
+        self.send_packet(&bundle)?;
+        
+        let mut console = self.console.write();
+        for (address, value) in &logged {
+            console.log_osc_sent(address, value);
+        }
+        
+        Ok(())
+    }
+    
+    /// Send already-encoded OSC bytes exactly as given, for message types we don't model.
+    /// The bytes must decode as one complete packet, or nothing is sent. Raw sends skip
+    /// any open batch, since adding them to it would mean re-encoding.
+    pub fn send_raw(&self, bytes: &[u8]) -> Result<()> {
+        let packet = match rosc::decoder::decode_udp(bytes) {
+            Ok(([], packet)) => packet,
+            Ok((rest, _)) => anyhow::bail!("{} bytes left over after the OSC packet", rest.len()),
+            Err(e) => anyhow::bail!("not a valid OSC packet: {}", e),
+        };
+        
+        self.send_encoded(bytes.to_vec(), message_count(&packet))?;
+        
+        let mut console = self.console.write();
+        for msg in packet_messages(&packet) {
+            console.log_osc_sent(&msg.addr, &format_osc_args(&msg.args));
+        }
+        
+        Ok(())
+    }
+    
     pub fn send_float(&self, address: &str, value: f32) -> Result<()> {
+        if self.send_only_on_change {
+            let last_sent = self.last_sent_floats.lock();
+            if last_sent.get(address).is_some_and(|last| (last - value).abs() < FLOAT_CHANGE_EPSILON) {
+                return Ok(());
+            }
+        }
+        
         let msg = OscMessage {
             addr: address.to_string(),
             args: vec![OscType::Float(value)],
         };
         
-        let packet = OscPacket::Message(msg);
-        let buf = rosc::encoder::encode(&packet)?;
+        self.send_packet(&OscPacket::Message(msg))?;
+        if self.send_only_on_change {
+            self.last_sent_floats.lock().insert(address.to_string(), value);
+        }
+        
+        // Log sent command
+        self.console.write().log_osc_sent(address, &format!("{}", value));
+        
+        Ok(())
+    }
+    
+    pub fn send_int(&self, address: &str, value: i32) -> Result<()> {
+        let msg = OscMessage {
+            addr: address.to_string(),
+            args: vec![OscType::Int(value)],
+        };
+        
+        self.send_packet(&OscPacket::Message(msg))?;
+        
+        // Log sent command
+        self.console.write().log_osc_sent(address, &format!("{}", value));
+        
+        Ok(())
+    }
+    
+    pub fn send_bool(&self, address: &str, value: bool) -> Result<()> {
+        let msg = OscMessage {
+            addr: address.to_string(),
+            args: vec![OscType::Bool(value)],
+        };
         
-        self.socket.send_to(&buf, &self.target_address)?;
+        self.send_packet(&OscPacket::Message(msg))?;
         
         // Log sent command
         self.console.write().log_osc_sent(address, &format!("{}", value));
@@ -161,10 +922,10 @@ impl OscManager {
             args: vec![OscType::String(value.to_string())],
         };
         
-        let packet = OscPacket::Message(msg);
-        let buf = rosc::encoder::encode(&packet)?;
+        self.send_packet(&OscPacket::Message(msg))?;
         
-        self.socket.send_to(&buf, &self.target_address)?;
+        // Log sent command
+        self.console.write().log_osc_sent(address, &format!("\"{}\"", value));
         
         Ok(())
     }
@@ -172,7 +933,17 @@ impl OscManager {
     // VRChat chatbox: /chatbox/input [string message] [bool send_immediately]
     // send_immediately=true -> sends message directly to chatbox
     // send_immediately=false -> opens keyboard with message pre-filled
+    // VRChat silently drops chatbox messages sent faster than ~1.3s apart, so sends
+    // inside the configured interval fail with OscError::ChatboxRateLimited
     pub fn send_chatbox(&self, message: &str, send_immediately: bool) -> Result<()> {
+        let mut last_send = self.last_chatbox_send.lock();
+        if let Some(last) = *last_send {
+            let elapsed = last.elapsed();
+            if elapsed < self.chatbox_interval {
+                return Err(OscError::ChatboxRateLimited(self.chatbox_interval - elapsed).into());
+            }
+        }
+        
         let msg = OscMessage {
             addr: "/chatbox/input".to_string(),
             args: vec![
@@ -181,14 +952,269 @@ impl OscManager {
             ],
         };
         
-        let packet = OscPacket::Message(msg);
-        let buf = rosc::encoder::encode(&packet)?;
-        
-        self.socket.send_to(&buf, &self.target_address)?;
+        self.send_packet(&OscPacket::Message(msg))?;
+        *last_send = Some(Instant::now());
         
         // Log sent command
         self.console.write().log_osc_sent("/chatbox/input", &format!("\"{}\" (immediate: {})", message, send_immediately));
         
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+impl Drop for OscManager {
+    fn drop(&mut self) {
+        self.shutdown();
+        
+        // Anything still waiting for a retry is abandoned. Set under the lock so the
+        // thread can't miss the wakeup between checking the flag and waiting.
+        {
+            let _pending = self.retries.pending.lock();
+            self.retries.shutdown.store(true, Ordering::Relaxed);
+            self.retries.wake.notify_one();
+        }
+        if let Some(thread) = self.retry_thread.lock().take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+// Messages in a packet, counting into nested bundles
+fn message_count(packet: &OscPacket) -> u64 {
+    match packet {
+        OscPacket::Message(_) => 1,
+        OscPacket::Bundle(bundle) => bundle.content.iter().map(message_count).sum(),
+    }
+}
+
+// Classic 16-bytes-per-line dump: offset, hex bytes, then printable ASCII with '.' for the rest
+fn hex_dump(bytes: &[u8]) -> String {
+    bytes.chunks(16).enumerate().map(|(line, chunk)| {
+        let hex: Vec<String> = chunk.iter().map(|b| format!("{:02x}", b)).collect();
+        let ascii: String = chunk.iter()
+            .map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' })
+            .collect();
+        format!("{:04x}  {:<47}  |{}|", line * 16, hex.join(" "), ascii)
+    }).collect::<Vec<_>>().join("\n")
+}
+
+// Every message in a packet, in order, flattening nested bundles
+fn packet_messages(packet: &OscPacket) -> Vec<&OscMessage> {
+    match packet {
+        OscPacket::Message(msg) => vec![msg],
+        OscPacket::Bundle(bundle) => bundle.content.iter().flat_map(packet_messages).collect(),
+    }
+}
+
+// (0, 1) is the OSC "immediately" time tag
+const IMMEDIATELY: OscTime = OscTime { seconds: 0, fractional: 1 };
+
+// Wall-clock time for an OSC time tag, or None for "immediately" and anything before
+// 1970. Done by hand because rosc's conversion panics on tags before the Unix epoch.
+fn osc_time_to_system_time(time: OscTime) -> Option<SystemTime> {
+    let seconds = u64::from(time.seconds).checked_sub(OSC_UNIX_OFFSET)?;
+    let nanos = (u64::from(time.fractional) * 1_000_000_000) >> 32;
+    Some(UNIX_EPOCH + Duration::new(seconds, nanos as u32))
+}
+
+/// Resolve "host:port" (including IPv6 literals like "[::1]:9000") to a socket address
+fn resolve_address(address: &str) -> Result<SocketAddr> {
+    address
+        .to_socket_addrs()
+        .with_context(|| format!("Invalid OSC address '{}'", address))?
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("OSC address '{}' did not resolve", address))
+}
+
+// A dual-stack IPv6 socket can only send to IPv4 hosts through v4-mapped addresses
+fn map_to_socket_family(target: SocketAddr, local_addr: Option<SocketAddr>) -> SocketAddr {
+    match (target, local_addr) {
+        (SocketAddr::V4(v4), Some(SocketAddr::V6(_))) => {
+            SocketAddr::V6(SocketAddrV6::new(v4.ip().to_ipv6_mapped(), v4.port(), 0, 0))
+        }
+        _ => target,
+    }
+}
+
+/// Whether a listener address contains OSC pattern-matching characters
+pub fn is_osc_pattern(address: &str) -> bool {
+    address.contains(['*', '?', '[', '{'])
+}
+
+/// Match an OSC address pattern against a concrete address, part by part.
+/// Supports `*`, `?`, `[abc]`, `[a-z]`, `[!abc]` and `{foo,bar}` as in the OSC 1.0 spec.
+pub fn osc_pattern_matches(pattern: &str, address: &str) -> bool {
+    let pattern_parts: Vec<&str> = pattern.split('/').collect();
+    let address_parts: Vec<&str> = address.split('/').collect();
+    
+    pattern_parts.len() == address_parts.len()
+        && pattern_parts.iter().zip(&address_parts).all(|(p, a)| {
+            let p: Vec<char> = p.chars().collect();
+            let a: Vec<char> = a.chars().collect();
+            match_part(&p, &a)
+        })
+}
+
+fn match_part(pattern: &[char], text: &[char]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some('*') => (0..=text.len()).any(|i| match_part(&pattern[1..], &text[i..])),
+        Some('?') => !text.is_empty() && match_part(&pattern[1..], &text[1..]),
+        Some('[') => {
+            let (end, c) = match (pattern.iter().position(|&c| c == ']'), text.first()) {
+                (Some(end), Some(&c)) => (end, c),
+                _ => return false,
+            };
+            
+            let mut set = &pattern[1..end];
+            let negate = set.first() == Some(&'!');
+            if negate {
+                set = &set[1..];
+            }
+            
+            let mut matched = false;
+            let mut i = 0;
+            while i < set.len() {
+                if i + 2 < set.len() && set[i + 1] == '-' {
+                    matched |= set[i] <= c && c <= set[i + 2];
+                    i += 3;
+                } else {
+                    matched |= set[i] == c;
+                    i += 1;
+                }
+            }
+            
+            matched != negate && match_part(&pattern[end + 1..], &text[1..])
+        }
+        Some('{') => {
+            let end = match pattern.iter().position(|&c| c == '}') {
+                Some(end) => end,
+                None => return false,
+            };
+            
+            let alternatives: String = pattern[1..end].iter().collect();
+            alternatives.split(',').any(|alt| {
+                let alt: Vec<char> = alt.chars().collect();
+                text.starts_with(&alt) && match_part(&pattern[end + 1..], &text[alt.len()..])
+            })
+        }
+        Some(&c) => text.first() == Some(&c) && match_part(&pattern[1..], &text[1..]),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    // Send-only so tests don't fight over a listen port
+    fn test_manager() -> OscManager {
+        let mut osc = crate::config::Config::default().osc;
+        osc.target_address = "127.0.0.1:9".to_string();
+        osc.enable_receive = false;
+        OscManager::new(&osc, Arc::new(RwLock::new(ConsoleLog::new()))).unwrap()
+    }
+    
+    #[test]
+    fn get_float_converts_numeric_types() {
+        let manager = test_manager();
+        manager.dispatch_local("/double", vec![OscType::Double(0.25)]);
+        manager.dispatch_local("/long", vec![OscType::Long(42)]);
+        manager.dispatch_local("/bool", vec![OscType::Bool(true)]);
+        
+        assert_eq!(manager.get_float("/double"), Some(0.25));
+        assert_eq!(manager.get_float("/long"), Some(42.0));
+        assert_eq!(manager.get_float("/bool"), Some(1.0));
+    }
+    
+    #[test]
+    fn get_float_rejects_out_of_range_longs() {
+        let manager = test_manager();
+        manager.dispatch_local("/long", vec![OscType::Long(1 << 40)]);
+        
+        assert_eq!(manager.get_float("/long"), None);
+        assert!(manager.get_value("/long").is_some());
+    }
+    
+    #[test]
+    fn future_bundle_waits_for_its_time_tag() {
+        let manager = test_manager();
+        let mut scheduler = Scheduler::default();
+        
+        // Whole seconds, so the tag converts back to exactly `due`
+        let unix_secs = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() + 60;
+        let due = UNIX_EPOCH + Duration::from_secs(unix_secs);
+        let timetag = OscTime { seconds: (unix_secs + OSC_UNIX_OFFSET) as u32, fractional: 0 };
+        let bundle = OscPacket::Bundle(OscBundle {
+            timetag,
+            content: vec![OscPacket::Message(OscMessage { addr: "/later".to_string(), args: vec![OscType::Int(1)] })],
+        });
+        
+        let handle = |packet, scheduler: &mut Scheduler| {
+            OscManager::handle_packet(packet, scheduler, &manager.listeners, &manager.values, &manager.observers, &manager.console);
+        };
+        handle(bundle, &mut scheduler);
+        
+        // Held, not dispatched
+        assert!(manager.get_value("/later").is_none());
+        assert!(scheduler.pop_due(due - Duration::from_secs(1)).is_none());
+        assert!(scheduler.time_until_next(SystemTime::now()).is_some_and(|wait| wait > Duration::from_secs(50)));
+        
+        // Once due it comes out and dispatches immediately
+        let packet = scheduler.pop_due(due).expect("bundle should be due");
+        handle(packet, &mut scheduler);
+        assert_eq!(manager.get_value("/later"), Some(vec![OscType::Int(1)]));
+        assert!(scheduler.time_until_next(due).is_none());
+    }
+    
+    #[test]
+    fn pattern_star_matches_any_suffix() {
+        let pattern = "/avatar/parameters/Face*";
+        assert!(osc_pattern_matches(pattern, "/avatar/parameters/Face"));
+        assert!(osc_pattern_matches(pattern, "/avatar/parameters/FaceSmile"));
+        assert!(osc_pattern_matches(pattern, "/avatar/parameters/FaceTracking_Jaw"));
+        assert!(!osc_pattern_matches(pattern, "/avatar/parameters/EyeFace"));
+        assert!(!osc_pattern_matches(pattern, "/avatar/parameters/face"));
+        // `*` stays within one address part
+        assert!(!osc_pattern_matches(pattern, "/avatar/parameters/Face/Smile"));
+        assert!(!osc_pattern_matches(pattern, "/avatar/Face"));
+    }
+    
+    #[test]
+    fn pattern_question_mark_matches_one_character() {
+        assert!(osc_pattern_matches("/input/Button?", "/input/Button1"));
+        assert!(!osc_pattern_matches("/input/Button?", "/input/Button"));
+        assert!(!osc_pattern_matches("/input/Button?", "/input/Button12"));
+    }
+    
+    #[test]
+    fn pattern_brackets_match_sets_and_ranges() {
+        assert!(osc_pattern_matches("/ch/[abc]", "/ch/b"));
+        assert!(!osc_pattern_matches("/ch/[abc]", "/ch/d"));
+        assert!(osc_pattern_matches("/ch/[0-9]", "/ch/7"));
+        assert!(!osc_pattern_matches("/ch/[0-9]", "/ch/x"));
+        assert!(osc_pattern_matches("/ch/[a-cx]", "/ch/x"));
+        assert!(!osc_pattern_matches("/ch/[0-9]", "/ch/"));
+    }
+    
+    #[test]
+    fn pattern_brackets_negate() {
+        assert!(osc_pattern_matches("/ch/[!0-9]", "/ch/x"));
+        assert!(!osc_pattern_matches("/ch/[!0-9]", "/ch/5"));
+        assert!(!osc_pattern_matches("/ch/[!abc]", "/ch/a"));
+    }
+    
+    #[test]
+    fn pattern_braces_match_alternatives() {
+        assert!(osc_pattern_matches("/avatar/{change,parameters}/x", "/avatar/change/x"));
+        assert!(osc_pattern_matches("/avatar/{change,parameters}/x", "/avatar/parameters/x"));
+        assert!(!osc_pattern_matches("/avatar/{change,parameters}/x", "/avatar/other/x"));
+    }
+    
+    #[test]
+    fn pattern_without_wildcards_needs_exact_address() {
+        assert!(!is_osc_pattern("/avatar/parameters/Face"));
+        assert!(osc_pattern_matches("/avatar/parameters/Face", "/avatar/parameters/Face"));
+        assert!(!osc_pattern_matches("/avatar/parameters/Face", "/avatar/parameters/Faces"));
+    }
+}
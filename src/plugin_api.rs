@@ -1,14 +1,118 @@
 use serde::{Deserialize, Serialize};
 
+/// Version of the host function interface. Bump this whenever a host function is
+/// removed or its signature/behaviour changes; plugins export the version they
+/// were built against from `plugin_abi_version()`.
+pub const HOST_ABI_VERSION: u32 = 1;
+
+/// Oldest plugin ABI version this host can still run
+pub const MIN_PLUGIN_ABI_VERSION: u32 = 1;
+
+/// Whether `address` is a usable OSC address: a leading '/', no empty parts, and
+/// only printable ASCII other than space and the pattern characters `#*,?[]{}`
+pub fn is_valid_osc_address(address: &str) -> bool {
+    match address.strip_prefix('/') {
+        Some(rest) => rest.split('/').all(|part| {
+            !part.is_empty()
+                && part.chars().all(|c| c.is_ascii_graphic() && !"#*,?[]{}".contains(c))
+        }),
+        None => false,
+    }
+}
+
+/// Map `value` out of `max` steps to the 2-decimal float a Unity animator reads back
+/// as that same step.
+///
+/// Unity truncates the parameter to 2 decimals and then takes
+/// `floor(param * max)`, so the float sent has to be `ceil(value * 100 / max) / 100`.
+/// `value` is clamped to `max`, and `max == 0` yields 0.0.
+pub fn normalized_value(value: u32, max: u32) -> f32 {
+    if value == 0 || max == 0 {
+        return 0.0;
+    }
+    let cents = (value.min(max) as u64 * 100).div_ceil(max as u64);
+    cents as f32 / 100.0
+}
+
+// `type_tag` values for the generic `plugin_on_osc(addr_ptr, addr_len, type_tag, value_bits)`
+// callback, and how `value_bits` is to be read for each
+
+/// Float or Double; `value_bits` is `f64::to_bits` of the value
+pub const OSC_TAG_FLOAT: i32 = 0;
+/// Int or Long; `value_bits` is the value as an `i64`
+pub const OSC_TAG_INT: i32 = 1;
+/// Bool; `value_bits` is 0 or 1
+pub const OSC_TAG_BOOL: i32 = 2;
+/// Color; `value_bits` is 0xRRGGBBAA
+pub const OSC_TAG_COLOR: i32 = 3;
+
+/// One message in the JSON array a plugin passes to `osc_send_bundle`, e.g.
+/// `{"addr":"/avatar/parameters/Hue","type":"float","value":0.5}`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleEntry {
+    pub addr: String,
+    #[serde(flatten)]
+    pub value: OscValue,
+}
+
+/// Typed OSC argument, tagged by `"type"` with the argument in `"value"`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
+pub enum OscValue {
+    Float(f32),
+    Int(i32),
+    Bool(bool),
+    String(String),
+}
+
 /// Information about a plugin
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PluginInfo {
     pub name: String,
     pub version: String,
     pub description: String,
+    /// Capabilities the plugin asks for, e.g. `"permissions":["fs","http","notify"]`
+    #[serde(default)]
+    pub permissions: Vec<Permission>,
+    /// Free-form tags describing what the plugin does, shown as badges in the Plugins
+    /// tab, e.g. `"capabilities":["sends_chatbox","listens_osc"]`. Purely informational.
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    /// Plugins this one loads and starts after, by the name in their plugin_info,
+    /// e.g. `"depends_on":["Boop Counter"]`
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+}
+
+/// Capabilities a plugin has to declare before the matching host functions will
+/// do anything. Undeclared calls are denied and logged, not trapped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Permission {
+    /// get_config_dir, fs_read and fs_write
+    #[serde(rename = "fs")]
+    Filesystem,
+    /// http_get
+    #[serde(rename = "http")]
+    Http,
+    /// notify
+    #[serde(rename = "notify")]
+    Notifications,
+}
+
+impl Permission {
+    pub fn description(&self) -> &'static str {
+        match self {
+            Permission::Filesystem => "Read and write files in its data folder",
+            Permission::Http => "Make HTTP requests",
+            Permission::Notifications => "Show desktop notifications",
+        }
+    }
 }
 
 /// UI configuration element types
+///
+/// Every element but `Separator` takes an optional `tooltip`, shown on hover, for explaining
+/// an option without a longer label.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum UiElement {
     TextInput {
@@ -16,15 +120,59 @@ pub enum UiElement {
         label: String,
         default_value: String,
         placeholder: String,
+        /// Whether the input holds an OSC address, which gets address completion and
+        /// is validated on Apply. When unset the host guesses from the default value
+        /// and placeholder.
+        #[serde(default)]
+        osc_address: Option<bool>,
+        #[serde(default)]
+        tooltip: Option<String>,
+    },
+    /// Numeric setting shown as a spin button. The value saved and sent in
+    /// ApplySettings is always a number within `min..=max`, with no decimals when
+    /// the range, default and step are all whole numbers.
+    NumberInput {
+        id: String,
+        label: String,
+        min: f64,
+        max: f64,
+        default: f64,
+        /// Increment for the +/- buttons, 1 if unset
+        #[serde(default)]
+        step: Option<f64>,
+        #[serde(default)]
+        tooltip: Option<String>,
     },
     Button {
         id: String,
         label: String,
+        /// If set, the host asks the user to confirm with this prompt before sending the click
+        #[serde(default)]
+        confirm: Option<String>,
+        #[serde(default)]
+        tooltip: Option<String>,
     },
     Label {
         text: String,
+        #[serde(default)]
+        tooltip: Option<String>,
+    },
+    /// Meter whose value the plugin sets with `ui_set_value(id, value)`; shown as
+    /// empty until the first value arrives
+    ProgressBar {
+        id: String,
+        min: f32,
+        max: f32,
+        #[serde(default)]
+        tooltip: Option<String>,
     },
     Separator,
+    /// Heading with a frame around every element after it, up to the next Section
+    Section {
+        title: String,
+        #[serde(default)]
+        tooltip: Option<String>,
+    },
 }
 
 /// UI configuration that plugins can provide
@@ -35,6 +183,11 @@ pub struct UiConfig {
 }
 
 /// Events from UI to plugin
+///
+/// Settings lifecycle: `plugin_load_config` runs once after loading and again each
+/// time the plugin's tab is opened. `ApplySettings` is saved to config by the host
+/// before it reaches the plugin, so after either call the saved config is
+/// authoritative and the plugin's in-memory copy should match it.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum UiEvent {
     ButtonClicked { id: String },
@@ -0,0 +1,88 @@
+use gtk4::prelude::*;
+use gtk4::Application;
+use ksni::menu::StandardItem;
+use ksni::{MenuItem, Tray, TrayService};
+use parking_lot::RwLock;
+use std::sync::Arc;
+use std::thread;
+
+use crate::console::ConsoleLog;
+
+/// StatusNotifierItem tray icon: click toggles the main window, the menu can also quit
+struct FoxTray;
+
+impl Tray for FoxTray {
+    fn id(&self) -> String {
+        "fox-osc".to_string()
+    }
+    
+    fn title(&self) -> String {
+        "Fox OSC".to_string()
+    }
+    
+    fn icon_name(&self) -> String {
+        "network-wireless".to_string()
+    }
+    
+    fn activate(&mut self, _x: i32, _y: i32) {
+        on_main_thread(toggle_window);
+    }
+    
+    fn menu(&self) -> Vec<MenuItem<Self>> {
+        vec![
+            StandardItem {
+                label: "Show/Hide".to_string(),
+                activate: Box::new(|_| on_main_thread(toggle_window)),
+                ..Default::default()
+            }
+            .into(),
+            MenuItem::Separator,
+            StandardItem {
+                label: "Quit".to_string(),
+                icon_name: "application-exit".to_string(),
+                // Goes through the app's shutdown handler, which stops plugins and saves config
+                activate: Box::new(|_| on_main_thread(|app| app.quit())),
+                ..Default::default()
+            }
+            .into(),
+        ]
+    }
+}
+
+/// Show the tray icon. Runs on its own thread; without a StatusNotifier host (no
+/// D-Bus session, or a desktop without tray support) this logs an error and shows
+/// the main window, which `start_minimized` would otherwise leave unreachable.
+pub fn spawn(console: Arc<RwLock<ConsoleLog>>) {
+    let service = TrayService::new(FoxTray);
+    thread::spawn(move || {
+        if let Err(e) = service.run() {
+            console.write().log_error(&format!("Tray icon unavailable: {}", e));
+            on_main_thread(show_window);
+        }
+    });
+}
+
+// Tray callbacks run on the D-Bus thread, GTK has to be touched from the main loop
+fn on_main_thread(f: fn(&Application)) {
+    glib::MainContext::default().invoke(move || {
+        if let Some(app) = gio::Application::default().and_downcast::<Application>() {
+            f(&app);
+        }
+    });
+}
+
+fn show_window(app: &Application) {
+    if let Some(window) = app.windows().first() {
+        window.present();
+    }
+}
+
+fn toggle_window(app: &Application) {
+    if let Some(window) = app.windows().first() {
+        if window.is_visible() {
+            window.set_visible(false);
+        } else {
+            window.present();
+        }
+    }
+}
@@ -1,16 +1,39 @@
 use gtk4::prelude::*;
 use gtk4::{
-    Application, ApplicationWindow, Box as GtkBox, Button, Entry, Label, Notebook, 
-    Orientation, Switch, Widget,
+    Application, ApplicationWindow, Box as GtkBox, Button, ButtonsType, DropDown, Entry, EntryCompletion, Label,
+    MessageDialog, MessageType, Notebook, Orientation, ResponseType, SpinButton, Switch, Widget,
 };
+use gtk4::StringList;
+use std::cell::Cell;
+use std::rc::Rc;
 use std::sync::Arc;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
 use glib;
 
 use crate::AppState;
-use crate::plugin_api::{UiElement, UiEvent};
+use crate::config::DEFAULT_PROFILE;
+use crate::config_watcher::ConfigWatcher;
+#[cfg(feature = "midi")]
+use crate::config::{MidiBinding, MidiEventKind};
+#[cfg(feature = "gamepad")]
+use crate::config::GamepadBinding;
+use crate::osc_manager::{OscManager, OscStats};
+use crate::plugin_api::{is_valid_osc_address, UiElement, UiEvent, HOST_ABI_VERSION};
+use crate::wasm_loader::{Compat, PluginMetrics, PLUGIN_HOOKS};
 use crate::console::create_console_ui;
 
+// Plugins tab shows a plugin's memory in red past this size
+const PLUGIN_MEMORY_WARNING_BYTES: usize = 32 * 1024 * 1024;
+
+// How often to check for config.toml changes, and how long it has to stay unchanged
+// before it's reloaded, so a burst of write/rename events only reloads once
+const CONFIG_WATCH_POLL: Duration = Duration::from_millis(250);
+const CONFIG_RELOAD_DEBOUNCE: Duration = Duration::from_millis(500);
+
+// Refresh rate for plugin progress bars; fast enough to look live
+const PROGRESS_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 #[allow(dead_code)]
 pub struct MainWindow {
     window: ApplicationWindow,
@@ -19,31 +42,105 @@ pub struct MainWindow {
 }
 
 impl MainWindow {
-    pub fn new(app: &Application, app_state: Arc<AppState>) -> Self {
+    pub fn new(app: &Application, app_state: Arc<AppState>, osc_manager: Arc<OscManager>) -> Self {
         let window = ApplicationWindow::new(app);
         window.set_title(Some("Fox OSC"));
-        window.set_default_size(800, 600);
+        let window_config = app_state.config.read().ui.window.clone();
+        window.set_default_size(window_config.width, window_config.height);
         
         let notebook = Notebook::new();
         
         // Console Log tab with new two-tab console
-        let (console_view, console_switch, _console_views) = create_console_ui(app_state.console.clone());
+        let (console_view, console_switch, _console_views) = create_console_ui(app_state.console.clone(), app_state.config.clone());
         notebook.append_page(&console_view, Some(&Label::new(Some("Console Log"))));
         
         // Plugins tab
         let plugins_tab = Self::create_plugins_tab(app_state.clone());
         notebook.append_page(&plugins_tab, Some(&Label::new(Some("Plugins"))));
         
+        // OSC settings tab
+        let osc_tab = Self::create_osc_settings_tab(app_state.clone(), osc_manager.clone());
+        notebook.append_page(&osc_tab, Some(&Label::new(Some("OSC Settings"))));
+        
+        // Manual send tab for testing avatars without a plugin
+        let send_tab = Self::create_send_tab(osc_manager.clone());
+        notebook.append_page(&send_tab, Some(&Label::new(Some("Send"))));
+        
+        // MIDI controller input
+        #[cfg(feature = "midi")]
+        {
+            let midi_tab = Self::create_midi_tab(app_state.clone(), osc_manager.clone());
+            notebook.append_page(&midi_tab, Some(&Label::new(Some("MIDI"))));
+        }
+        
+        // Game controller input
+        #[cfg(feature = "gamepad")]
+        {
+            let gamepad_tab = Self::create_gamepad_tab(app_state.clone(), osc_manager.clone());
+            notebook.append_page(&gamepad_tab, Some(&Label::new(Some("Gamepad"))));
+        }
+        
         // Add plugin-specific tabs from UI configs
         let plugin_loader = app_state.plugin_loader.read();
         for (idx, plugin) in plugin_loader.plugins().iter().enumerate() {
             if let Some(ui_config) = plugin.ui_config() {
-                let plugin_tab = Self::create_plugin_ui_tab(ui_config, idx, plugin.info().name.clone(), app_state.clone());
-                notebook.append_page(&plugin_tab, Some(&Label::new(Some(&ui_config.title))));
+                let plugin_tab = Self::create_plugin_ui_tab(ui_config, idx, plugin.name().to_string(), app_state.clone());
+                let tab_title = match plugin.instance_id() {
+                    Some(id) => format!("{} ({})", ui_config.title, id),
+                    None => ui_config.title.clone(),
+                };
+                notebook.append_page(&plugin_tab, Some(&Label::new(Some(&tab_title))));
             }
         }
         drop(plugin_loader);
         
+        // Reopen on the tab that was last selected, if it still exists
+        if window_config.last_tab < notebook.n_pages() {
+            notebook.set_current_page(Some(window_config.last_tab));
+        }
+        
+        let app_state_clone = app_state.clone();
+        notebook.connect_switch_page(move |_, _, page| {
+            let mut config = app_state_clone.config.write();
+            config.ui.window.last_tab = page;
+            // Written by the update loop or on shutdown, not on every click
+            config.mark_dirty();
+        });
+        
+        // Offer to stop plugins and write pending settings before closing, so a failed
+        // save shows up in the console while it can still be read
+        let app_state_clone = app_state.clone();
+        let confirmed = Rc::new(Cell::new(false));
+        window.connect_close_request(move |window| {
+            if !confirmed.get() {
+                if let Some(message) = Self::unsaved_state_message(&app_state_clone) {
+                    let app_state = app_state_clone.clone();
+                    let confirmed = confirmed.clone();
+                    let window_clone = window.clone();
+                    Self::confirm(window, "Save before quitting?", &message, "Save and Quit", move || {
+                        // Either way the next close doesn't ask again, so a plugin that
+                        // keeps failing to stop can't keep the window open
+                        confirmed.set(true);
+                        if Self::stop_plugins_and_flush(&app_state) {
+                            window_clone.close();
+                        } else {
+                            app_state.console.write().log_error("Not everything could be saved; close the window again to quit anyway");
+                        }
+                    });
+                    return glib::Propagation::Stop;
+                }
+            }
+            
+            // Window size is recorded once on close rather than on every resize step, and
+            // written with everything else pending when the app shuts down
+            let (width, height) = window.default_size();
+            let mut config = app_state_clone.config.write();
+            config.ui.window.width = width;
+            config.ui.window.height = height;
+            config.mark_dirty();
+            glib::Propagation::Proceed
+        });
+        
         window.set_child(Some(&notebook));
         
         // Connect console switch to save config
@@ -62,7 +159,31 @@ impl MainWindow {
             glib::Propagation::Proceed
         });
         
-        window.present();
+        // Pick up hand edits to config.toml while running
+        match ConfigWatcher::new() {
+            Ok(watcher) => {
+                let app_state_clone = app_state.clone();
+                let console_switch_watch = console_switch_clone.clone();
+                let mut changed_at: Option<Instant> = None;
+                glib::timeout_add_local(CONFIG_WATCH_POLL, move || {
+                    if watcher.take_changed() {
+                        changed_at = Some(Instant::now());
+                    }
+                    if changed_at.is_some_and(|at| at.elapsed() >= CONFIG_RELOAD_DEBOUNCE) {
+                        changed_at = None;
+                        Self::reload_config(&app_state_clone, &console_switch_watch);
+                    }
+                    glib::ControlFlow::Continue
+                });
+            }
+            Err(e) => app_state.console.write().log_error(&format!("Failed to watch config.toml for changes: {}", e)),
+        }
+        
+        // With a tray icon to bring it back, the window may start hidden. If the tray
+        // fails to start it presents the window itself.
+        if !(cfg!(feature = "tray") && app_state.config.read().ui.start_minimized) {
+            window.present();
+        }
         
         Self {
             window,
@@ -80,6 +201,26 @@ impl MainWindow {
         
         // Store input widgets by ID
         let mut input_widgets: HashMap<String, Entry> = HashMap::new();
+        let mut number_widgets: HashMap<String, SpinButton> = HashMap::new();
+        // Declared default per input id, for Reset to Defaults
+        let mut input_defaults: Vec<(String, String)> = Vec::new();
+        // Ids of inputs that hold an OSC address and get validated on Apply
+        let mut address_inputs: HashSet<String> = HashSet::new();
+        // Progress bars with their id and range, fed from the plugin's ui_set_value calls
+        let mut progress_bars: Vec<(String, f32, f32, gtk4::ProgressBar)> = Vec::new();
+        // Completion list shared by all address inputs, refilled while the tab is shown
+        let address_completions = gtk4::ListStore::new(&[glib::Type::STRING]);
+        
+        // New addresses keep arriving while the tab is open
+        let app_state_timer = app_state.clone();
+        let completions_timer = address_completions.clone();
+        let vbox_timer = vbox.clone();
+        glib::timeout_add_seconds_local(5, move || {
+            if vbox_timer.is_mapped() {
+                Self::fill_address_completions(&completions_timer, &app_state_timer);
+            }
+            glib::ControlFlow::Continue
+        });
         
         // SPECIAL: For Boop Counter, add live updating counters at the top
         if plugin_name == "Boop Counter" {
@@ -125,15 +266,37 @@ impl MainWindow {
             });
         }
         
+        // Elements go into the tab itself until the first Section, then into that section
+        let mut container = vbox.clone();
         for element in &ui_config.elements {
             match element {
-                UiElement::Label { text } => {
+                UiElement::Section { title, tooltip } => {
+                    let heading = Label::new(None);
+                    heading.set_markup(&format!("<b>{}</b>", glib::markup_escape_text(title)));
+                    heading.set_tooltip_text(tooltip.as_deref());
+                    let frame = gtk4::Frame::new(None);
+                    frame.set_label_widget(Some(&heading));
+                    frame.set_margin_top(10);
+                    
+                    let section_box = GtkBox::new(Orientation::Vertical, 10);
+                    section_box.set_margin_top(10);
+                    section_box.set_margin_bottom(10);
+                    section_box.set_margin_start(10);
+                    section_box.set_margin_end(10);
+                    frame.set_child(Some(&section_box));
+                    
+                    vbox.append(&frame);
+                    container = section_box;
+                }
+                UiElement::Label { text, tooltip } => {
                     let label = Label::new(Some(text));
                     label.set_halign(gtk4::Align::Start);
-                    vbox.append(&label);
+                    label.set_tooltip_text(tooltip.as_deref());
+                    container.append(&label);
                 }
-                UiElement::TextInput { id, label, default_value, placeholder } => {
+                UiElement::TextInput { id, label, default_value, placeholder, osc_address, tooltip } => {
                     let hbox = GtkBox::new(Orientation::Horizontal, 10);
+                    hbox.set_tooltip_text(tooltip.as_deref());
                     
                     let label_widget = Label::new(Some(label));
                     label_widget.set_width_chars(15);
@@ -143,82 +306,913 @@ impl MainWindow {
                     let entry = Entry::new();
                     
                     // Load saved value from config or use default
-                    let config = app_state.config.read();
-                    let config_key = format!("{}_address", id);
-                    if let Some(saved_value) = config.get_plugin_setting(&plugin_name, &config_key) {
+                    if let Some(saved_value) = Self::saved_input_value(&app_state, &plugin_name, id) {
                         entry.set_text(&saved_value);
                     } else {
                         entry.set_text(default_value);
                     }
-                    drop(config);
                     
                     entry.set_placeholder_text(Some(placeholder));
                     entry.set_hexpand(true);
                     hbox.append(&entry);
                     
+                    let is_address = osc_address.unwrap_or_else(|| {
+                        default_value.starts_with('/') || placeholder.to_lowercase().contains("osc address")
+                    });
+                    if is_address {
+                        address_inputs.insert(id.clone());
+                        
+                        let completion = EntryCompletion::new();
+                        completion.set_model(Some(&address_completions));
+                        completion.set_text_column(0);
+                        completion.set_minimum_key_length(1);
+                        entry.set_completion(Some(&completion));
+                    }
+                    input_defaults.push((id.clone(), default_value.clone()));
                     input_widgets.insert(id.clone(), entry.clone());
-                    vbox.append(&hbox);
+                    container.append(&hbox);
                 }
-                UiElement::Button { id, label } => {
+                UiElement::NumberInput { id, label, min, max, default, step, tooltip } => {
+                    let hbox = GtkBox::new(Orientation::Horizontal, 10);
+                    hbox.set_tooltip_text(tooltip.as_deref());
+                    
+                    let label_widget = Label::new(Some(label));
+                    label_widget.set_width_chars(15);
+                    label_widget.set_halign(gtk4::Align::Start);
+                    hbox.append(&label_widget);
+                    
+                    // GTK needs min <= max and a non-zero step
+                    let (min, max) = if min <= max { (*min, *max) } else { (*max, *min) };
+                    let step = step.filter(|step| *step > 0.0).unwrap_or(1.0);
+                    let spin = SpinButton::with_range(min, max, step);
+                    let whole = [min, max, *default, step].iter().all(|v| v.fract() == 0.0);
+                    spin.set_digits(if whole { 0 } else { 2 });
+                    spin.set_hexpand(true);
+                    
+                    // SpinButton clamps, so the default recorded here is in range too
+                    spin.set_value(*default);
+                    input_defaults.push((id.clone(), Self::spin_text(&spin)));
+                    if let Some(saved) = Self::saved_input_value(&app_state, &plugin_name, id).and_then(|v| v.parse::<f64>().ok()) {
+                        spin.set_value(saved);
+                    }
+                    
+                    hbox.append(&spin);
+                    number_widgets.insert(id.clone(), spin);
+                    container.append(&hbox);
+                }
+                UiElement::Button { id, label, confirm, tooltip } => {
                     let button = Button::with_label(label);
+                    button.set_tooltip_text(tooltip.as_deref());
                     button.set_halign(gtk4::Align::End);
                     
                     let app_state_clone = app_state.clone();
                     let button_id = id.clone();
-                    button.connect_clicked(move |_| {
-                        // Send button click event to plugin
+                    let button_label = label.clone();
+                    let confirm = confirm.clone();
+                    button.connect_clicked(move |button| {
+                        // Send button click event to plugin, asking first for destructive buttons
                         let event = UiEvent::ButtonClicked { id: button_id.clone() };
-                        if let Ok(event_json) = serde_json::to_string(&event) {
-                            let mut loader = app_state_clone.plugin_loader.write();
-                            if let Some(plugin) = loader.plugins_mut().get_mut(plugin_idx) {
-                                if let Err(e) = plugin.send_ui_event(&event_json) {
-                                    app_state_clone.console.write().log_error(&format!("Failed to send UI event: {}", e));
-                                }
+                        match &confirm {
+                            Some(prompt) => {
+                                let app_state_dialog = app_state_clone.clone();
+                                Self::confirm(button, &button_label, prompt, &button_label, move || {
+                                    Self::send_plugin_event(&app_state_dialog, plugin_idx, &event);
+                                });
                             }
+                            None => Self::send_plugin_event(&app_state_clone, plugin_idx, &event),
                         }
                     });
                     
-                    vbox.append(&button);
+                    container.append(&button);
+                }
+                UiElement::ProgressBar { id, min, max, tooltip } => {
+                    let progress_bar = gtk4::ProgressBar::new();
+                    progress_bar.set_tooltip_text(tooltip.as_deref());
+                    progress_bar.set_show_text(true);
+                    progress_bar.set_text(Some(""));
+                    container.append(&progress_bar);
+                    progress_bars.push((id.clone(), *min, *max, progress_bar));
                 }
                 UiElement::Separator => {
                     let separator = gtk4::Separator::new(Orientation::Horizontal);
                     separator.set_margin_top(10);
                     separator.set_margin_bottom(10);
-                    vbox.append(&separator);
+                    container.append(&separator);
                 }
             }
         }
         
+        // Plugins push values whenever they like; show the latest while the tab is visible
+        if !progress_bars.is_empty() {
+            let ui_values = app_state.plugin_loader.read().plugins().get(plugin_idx).map(|plugin| plugin.ui_values());
+            if let Some(ui_values) = ui_values {
+                let vbox_timer = vbox.clone();
+                glib::timeout_add_local(PROGRESS_POLL_INTERVAL, move || {
+                    if vbox_timer.is_mapped() {
+                        let values = ui_values.lock();
+                        for (id, min, max, progress_bar) in &progress_bars {
+                            if let Some(value) = values.get(id) {
+                                let fraction = if max > min { ((value - min) / (max - min)).clamp(0.0, 1.0) } else { 0.0 };
+                                progress_bar.set_fraction(fraction as f64);
+                                progress_bar.set_text(Some(&format!("{:.0}%", fraction * 100.0)));
+                            }
+                        }
+                    }
+                    glib::ControlFlow::Continue
+                });
+            }
+        }
+        
+        // Whenever the tab is shown, re-sync both sides from the saved config: the entries
+        // show what's stored and the plugin reloads it, dropping any unapplied edits
+        let app_state_map = app_state.clone();
+        let plugin_name_map = plugin_name.clone();
+        let input_widgets_map = input_widgets.clone();
+        let number_widgets_map = number_widgets.clone();
+        vbox.connect_map(move |_| {
+            Self::fill_address_completions(&address_completions, &app_state_map);
+            
+            for (id, entry) in &input_widgets_map {
+                if let Some(saved_value) = Self::saved_input_value(&app_state_map, &plugin_name_map, id) {
+                    entry.set_text(&saved_value);
+                }
+            }
+            for (id, spin) in &number_widgets_map {
+                if let Some(saved) = Self::saved_input_value(&app_state_map, &plugin_name_map, id).and_then(|v| v.parse::<f64>().ok()) {
+                    spin.set_value(saved);
+                }
+            }
+            
+            let mut loader = app_state_map.plugin_loader.write();
+            if let Some(plugin) = loader.plugins_mut().get_mut(plugin_idx) {
+                if let Err(e) = plugin.load_config_from_disk() {
+                    app_state_map.console.write().log_error(&format!("Failed to reload config for {}: {}", plugin_name_map, e));
+                }
+            }
+        });
+        
         // Add an "Apply" button at the bottom to send all values
         let apply_button = Button::with_label("Apply Changes");
-        apply_button.set_halign(gtk4::Align::End);
-        apply_button.set_margin_top(10);
+        
+        let error_label = Label::new(None);
+        error_label.set_halign(gtk4::Align::End);
+        error_label.set_wrap(true);
         
         let app_state_clone = app_state.clone();
+        let error_label_clone = error_label.clone();
+        let input_widgets_reset = input_widgets.clone();
+        let number_widgets_reset = number_widgets.clone();
         apply_button.connect_clicked(move |_| {
             // Collect all input values
             let mut values = Vec::new();
+            let mut invalid = Vec::new();
             for (id, entry) in &input_widgets {
-                values.push((id.clone(), entry.text().to_string()));
+                let value = entry.text().to_string();
+                if address_inputs.contains(id) && !is_valid_osc_address(&value) {
+                    entry.add_css_class("error");
+                    invalid.push(value.clone());
+                } else {
+                    entry.remove_css_class("error");
+                }
+                values.push((id.clone(), value));
+            }
+            for (id, spin) in &number_widgets {
+                // Commit anything typed but not yet parsed, clamped to the range
+                spin.update();
+                values.push((id.clone(), Self::spin_text(spin)));
+            }
+            
+            // Don't save anything while an address is invalid, it would just never match
+            if !invalid.is_empty() {
+                error_label_clone.set_markup(&format!(
+                    "<span foreground='red'>Invalid OSC address: {}. Addresses start with '/' and contain no spaces.</span>",
+                    glib::markup_escape_text(&invalid.join(", "))
+                ));
+                return;
             }
+            error_label_clone.set_text("");
             
-            // Send apply event to plugin
+            // Send apply event to plugin; the host saves each value under its id
             let event = UiEvent::ApplySettings { values };
-            if let Ok(event_json) = serde_json::to_string(&event) {
-                let mut loader = app_state_clone.plugin_loader.write();
-                if let Some(plugin) = loader.plugins_mut().get_mut(plugin_idx) {
-                    if let Err(e) = plugin.send_ui_event(&event_json) {
-                        app_state_clone.console.write().log_error(&format!("Failed to send UI event: {}", e));
+            Self::send_plugin_event(&app_state_clone, plugin_idx, &event);
+        });
+        
+        // Put every input back to the plugin's declared default, after confirming
+        let reset_button = Button::with_label("Reset to Defaults");
+        
+        let app_state_clone = app_state.clone();
+        let error_label_reset = error_label.clone();
+        reset_button.connect_clicked(move |button| {
+            let app_state_dialog = app_state_clone.clone();
+            let input_widgets_dialog = input_widgets_reset.clone();
+            let number_widgets_dialog = number_widgets_reset.clone();
+            let input_defaults_dialog = input_defaults.clone();
+            let error_label_dialog = error_label_reset.clone();
+            Self::confirm(
+                button,
+                "Reset to defaults?",
+                "All settings on this tab will be replaced with the plugin's defaults.",
+                "Reset",
+                move || {
+                    for (id, default_value) in &input_defaults_dialog {
+                        if let Some(entry) = input_widgets_dialog.get(id) {
+                            entry.set_text(default_value);
+                            entry.remove_css_class("error");
+                        }
+                        if let (Some(spin), Ok(value)) = (number_widgets_dialog.get(id), default_value.parse::<f64>()) {
+                            spin.set_value(value);
+                        }
+                    }
+                    error_label_dialog.set_text("");
+                    
+                    // Saving happens in send_ui_event, same as a normal Apply
+                    let event = UiEvent::ApplySettings { values: input_defaults_dialog.clone() };
+                    Self::send_plugin_event(&app_state_dialog, plugin_idx, &event);
+                },
+            );
+        });
+        
+        let button_box = GtkBox::new(Orientation::Horizontal, 10);
+        button_box.set_halign(gtk4::Align::End);
+        button_box.set_margin_top(10);
+        button_box.append(&reset_button);
+        button_box.append(&apply_button);
+        vbox.append(&button_box);
+        vbox.append(&error_label);
+        
+        vbox.upcast::<Widget>()
+    }
+    
+    // A NumberInput's value as saved and sent to the plugin, e.g. "5" or "0.25"
+    fn spin_text(spin: &SpinButton) -> String {
+        format!("{:.*}", spin.digits() as usize, spin.value())
+    }
+    
+    // Offer every address seen so far or imported from an avatar config
+    fn fill_address_completions(store: &gtk4::ListStore, app_state: &AppState) {
+        let addresses = app_state.console.read().address_suggestions();
+        // Addresses are only ever added, so an unchanged count means an unchanged list
+        if store.iter_n_children(None) as usize == addresses.len() {
+            return;
+        }
+        
+        store.clear();
+        for address in addresses {
+            store.set(&store.append(), &[(0, &address)]);
+        }
+    }
+    
+    fn show_plugin_error(label: &Label, error: Option<&str>) {
+        match error {
+            Some(error) => label.set_markup(&format!("<span foreground='red'>\u{26a0} {}</span>", glib::markup_escape_text(error))),
+            None => label.set_text(""),
+        }
+    }
+    
+    // e.g. "OSC: 1203 in / 87 out, last recv 0.2s ago", plus any send trouble in red
+    fn osc_stats_markup(stats: &OscStats) -> String {
+        let last_receive = match stats.last_receive {
+            Some(at) => format!("last recv {:.1}s ago", at.elapsed().as_secs_f64()),
+            None => "nothing received yet".to_string(),
+        };
+        let mut markup = format!("OSC: {} in / {} out, {}", stats.messages_received, stats.messages_sent, last_receive);
+        
+        if stats.dropped_sends > 0 {
+            markup.push_str(&format!(", <span foreground='red'>{} dropped</span>", stats.dropped_sends));
+        }
+        if let Some(error) = &stats.last_send_error {
+            markup.push_str(&format!(
+                "\n<span foreground='red'>Last send error: {}</span>",
+                glib::markup_escape_text(error)
+            ));
+        }
+        markup
+    }
+    
+    fn usage_markup(metrics: PluginMetrics, memory_size: usize) -> String {
+        let tick_text = if metrics.ticks == 0 {
+            "avg - µs/tick".to_string()
+        } else {
+            format!("avg {:.0} µs/tick", metrics.avg_us)
+        };
+        
+        // Linear memory never shrinks, so steady growth means a leaking allocator
+        let memory_mib = memory_size as f64 / (1024.0 * 1024.0);
+        let memory_text = if memory_size >= PLUGIN_MEMORY_WARNING_BYTES {
+            format!("<span foreground='red'>{:.1} MiB memory</span>", memory_mib)
+        } else {
+            format!("{:.1} MiB memory", memory_mib)
+        };
+        
+        format!("<span size='small'>{} · {}</span>", tick_text, memory_text)
+    }
+    
+    // Ask before doing something that can't be undone; on_accept runs only on confirm
+    fn confirm<F: Fn() + 'static>(widget: &impl IsA<Widget>, title: &str, message: &str, accept_label: &str, on_accept: F) {
+        let dialog = MessageDialog::builder()
+            .modal(true)
+            .message_type(MessageType::Question)
+            .buttons(ButtonsType::None)
+            .text(title)
+            .secondary_text(message)
+            .build();
+        if let Some(window) = widget.root().and_downcast::<gtk4::Window>() {
+            dialog.set_transient_for(Some(&window));
+        }
+        dialog.add_button("Cancel", ResponseType::Cancel);
+        dialog.add_button(accept_label, ResponseType::Accept);
+        
+        dialog.connect_response(move |dialog, response| {
+            dialog.close();
+            if response == ResponseType::Accept {
+                on_accept();
+            }
+        });
+        
+        dialog.present();
+    }
+    
+    // What would be lost by quitting right now, or None if nothing would be
+    fn unsaved_state_message(app_state: &AppState) -> Option<String> {
+        let unsaved_plugins: Vec<String> = app_state.plugin_loader.read().plugins()
+            .iter()
+            .filter(|plugin| plugin.has_unsaved_state())
+            .map(|plugin| plugin.name().to_string())
+            .collect();
+        let config_dirty = app_state.config.read().is_dirty();
+        
+        let mut lines = Vec::new();
+        if config_dirty {
+            lines.push("Some settings changes haven't been written to config.toml yet.".to_string());
+        }
+        if !unsaved_plugins.is_empty() {
+            lines.push(format!("Plugins with unsaved state: {}.", unsaved_plugins.join(", ")));
+        }
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+    
+    // Stop every running plugin so it can save, then write the config. Returns false,
+    // after logging why, if anything failed.
+    fn stop_plugins_and_flush(app_state: &AppState) -> bool {
+        let mut ok = true;
+        let mut loader = app_state.plugin_loader.write();
+        for plugin in loader.plugins_mut().iter_mut().filter(|plugin| plugin.is_running()) {
+            if let Err(e) = plugin.stop() {
+                app_state.console.write().log_error(&format!("Failed to stop plugin '{}': {}", plugin.name(), e));
+                ok = false;
+            }
+        }
+        drop(loader);
+        
+        if let Err(e) = app_state.config.write().flush() {
+            app_state.console.write().log_error(&format!("Failed to save config: {}", e));
+            ok = false;
+        }
+        ok
+    }
+    
+    // Apply an externally edited config.toml. Invalid edits are reported and the
+    // current settings kept, so a half-saved file doesn't reset anything.
+    fn reload_config(app_state: &AppState, console_switch: &Switch) {
+        let result = app_state.config.read().load_if_changed();
+        let config = match result {
+            Ok(Some(config)) => config,
+            Ok(None) => return,
+            Err(e) => {
+                app_state.console.write().log_error(&format!("Failed to reload config.toml: {}", e));
+                return;
+            }
+        };
+        
+        let console_enabled = config.ui.console_enabled;
+        *app_state.config.write() = config;
+        app_state.console.write().set_enabled(console_enabled);
+        if console_switch.is_active() != console_enabled {
+            console_switch.set_active(console_enabled);
+        }
+        
+        let mut loader = app_state.plugin_loader.write();
+        for plugin in loader.plugins_mut().iter_mut().filter(|plugin| plugin.is_running()) {
+            if let Err(e) = plugin.load_config_from_disk() {
+                app_state.console.write().log_error(&format!("Failed to reload config for {}: {}", plugin.name(), e));
+            }
+        }
+        drop(loader);
+        
+        app_state.console.write().log_info("Reloaded config.toml after an external change");
+    }
+    
+    fn send_plugin_event(app_state: &AppState, plugin_idx: usize, event: &UiEvent) {
+        if let Ok(event_json) = serde_json::to_string(event) {
+            let mut loader = app_state.plugin_loader.write();
+            if let Some(plugin) = loader.plugins_mut().get_mut(plugin_idx) {
+                if let Err(e) = plugin.send_ui_event(&event_json) {
+                    app_state.console.write().log_error(&format!("Failed to send UI event: {}", e));
+                }
+            }
+        }
+    }
+    
+    // Saved value for a plugin TextInput. Values are stored under the input id;
+    // older configs used "<id>_address".
+    fn saved_input_value(app_state: &AppState, plugin_name: &str, id: &str) -> Option<String> {
+        let config = app_state.config.read();
+        config.get_plugin_setting(plugin_name, id)
+            .or_else(|| config.get_plugin_setting(plugin_name, &format!("{}_address", id)))
+    }
+    
+    fn create_osc_settings_tab(app_state: Arc<AppState>, osc_manager: Arc<OscManager>) -> Widget {
+        let vbox = GtkBox::new(Orientation::Vertical, 10);
+        vbox.set_margin_top(20);
+        vbox.set_margin_bottom(20);
+        vbox.set_margin_start(20);
+        vbox.set_margin_end(20);
+        
+        let title = Label::new(None);
+        title.set_markup("<span size='x-large' weight='bold'>OSC Connection</span>");
+        title.set_halign(gtk4::Align::Start);
+        vbox.append(&title);
+        
+        // Live traffic counts, to check VRChat is actually talking to us
+        let stats_label = Label::new(None);
+        stats_label.set_halign(gtk4::Align::Start);
+        stats_label.set_wrap(true);
+        stats_label.set_markup(&Self::osc_stats_markup(&osc_manager.stats()));
+        vbox.append(&stats_label);
+        
+        let osc_manager_timer = osc_manager.clone();
+        let stats_label_timer = stats_label.clone();
+        glib::timeout_add_seconds_local(1, move || {
+            if stats_label_timer.is_mapped() {
+                stats_label_timer.set_markup(&Self::osc_stats_markup(&osc_manager_timer.stats()));
+            }
+            glib::ControlFlow::Continue
+        });
+        
+        let osc_config = app_state.config.read().effective_osc();
+        let bind_entry = Entry::new();
+        bind_entry.set_text(&osc_config.bind_address);
+        let target_entry = Entry::new();
+        target_entry.set_text(&osc_config.target_address);
+        target_entry.set_placeholder_text(Some("host:port, comma-separated for several"));
+        
+        for (label, entry) in [("Listen on:", &bind_entry), ("Send to:", &target_entry)] {
+            let hbox = GtkBox::new(Orientation::Horizontal, 10);
+            
+            let label_widget = Label::new(Some(label));
+            label_widget.set_width_chars(15);
+            label_widget.set_halign(gtk4::Align::Start);
+            hbox.append(&label_widget);
+            
+            entry.set_hexpand(true);
+            hbox.append(entry);
+            vbox.append(&hbox);
+        }
+        
+        let status_label = Label::new(None);
+        status_label.set_halign(gtk4::Align::Start);
+        status_label.set_wrap(true);
+        
+        let apply_button = Button::with_label("Apply Changes");
+        apply_button.set_halign(gtk4::Align::End);
+        apply_button.set_margin_top(10);
+        
+        let status_clone = status_label.clone();
+        apply_button.connect_clicked(move |_| {
+            let bind_address = bind_entry.text().to_string();
+            let target_address = target_entry.text().to_string();
+            
+            // Check the target before moving the socket, so a bad target can't leave
+            // the socket rebound while the config still has the old address
+            let result = OscManager::validate_target(&target_address)
+                .and_then(|_| osc_manager.rebind(&bind_address))
+                .and_then(|_| osc_manager.set_target(&target_address));
+            
+            match result {
+                Ok(()) => {
+                    let mut config = app_state.config.write();
+                    config.osc.bind_address = bind_address;
+                    config.osc.target_address = target_address;
+                    // Explicitly applied values replace whatever came from the command line
+                    config.overrides = Default::default();
+                    if let Err(e) = config.save() {
+                        app_state.console.write().log_error(&format!("Failed to save config: {}", e));
                     }
+                    status_clone.set_text("Applied");
+                }
+                Err(e) => {
+                    app_state.console.write().log_error(&format!("Failed to apply OSC settings: {}", e));
+                    status_clone.set_markup(&format!("<span foreground='red'>{}</span>", glib::markup_escape_text(&e.to_string())));
                 }
             }
         });
         
         vbox.append(&apply_button);
+        vbox.append(&status_label);
         
         vbox.upcast::<Widget>()
     }
     
+    fn create_send_tab(osc_manager: Arc<OscManager>) -> Widget {
+        let vbox = GtkBox::new(Orientation::Vertical, 10);
+        vbox.set_margin_top(20);
+        vbox.set_margin_bottom(20);
+        vbox.set_margin_start(20);
+        vbox.set_margin_end(20);
+        
+        let title = Label::new(None);
+        title.set_markup("<span size='x-large' weight='bold'>Send OSC Message</span>");
+        title.set_halign(gtk4::Align::Start);
+        vbox.append(&title);
+        
+        let hbox = GtkBox::new(Orientation::Horizontal, 10);
+        
+        let address_entry = Entry::new();
+        address_entry.set_placeholder_text(Some("/avatar/parameters/..."));
+        address_entry.set_hexpand(true);
+        hbox.append(&address_entry);
+        
+        let type_dropdown = DropDown::from_strings(&["float", "int", "bool", "string"]);
+        hbox.append(&type_dropdown);
+        
+        let value_entry = Entry::new();
+        value_entry.set_placeholder_text(Some("Value"));
+        hbox.append(&value_entry);
+        
+        let send_button = Button::with_label("Send");
+        hbox.append(&send_button);
+        vbox.append(&hbox);
+        
+        let status_label = Label::new(None);
+        status_label.set_halign(gtk4::Align::Start);
+        vbox.append(&status_label);
+        
+        let status_clone = status_label.clone();
+        send_button.connect_clicked(move |_| {
+            let address = address_entry.text().to_string();
+            let value = value_entry.text().trim().to_string();
+            
+            let result = match type_dropdown.selected() {
+                0 => value.parse::<f32>()
+                    .map_err(|_| anyhow::anyhow!("'{}' is not a float", value))
+                    .and_then(|v| osc_manager.send_float(&address, v)),
+                1 => value.parse::<i32>()
+                    .map_err(|_| anyhow::anyhow!("'{}' is not an int", value))
+                    .and_then(|v| osc_manager.send_int(&address, v)),
+                2 => match value.to_lowercase().as_str() {
+                    "true" | "1" => osc_manager.send_bool(&address, true),
+                    "false" | "0" => osc_manager.send_bool(&address, false),
+                    _ => Err(anyhow::anyhow!("'{}' is not a bool (use true/false)", value)),
+                },
+                _ => osc_manager.send_string(&address, &value),
+            };
+            
+            match result {
+                Ok(()) => status_clone.set_text("Sent"),
+                Err(e) => status_clone.set_markup(&format!("<span foreground='red'>{}</span>", glib::markup_escape_text(&e.to_string()))),
+            }
+        });
+        
+        vbox.upcast::<Widget>()
+    }
+    
+    #[cfg(feature = "midi")]
+    fn create_midi_tab(app_state: Arc<AppState>, osc_manager: Arc<OscManager>) -> Widget {
+        use crate::midi::MidiBridge;
+        
+        let vbox = GtkBox::new(Orientation::Vertical, 10);
+        vbox.set_margin_top(20);
+        vbox.set_margin_bottom(20);
+        vbox.set_margin_start(20);
+        vbox.set_margin_end(20);
+        
+        let title = Label::new(None);
+        title.set_markup("<span size='x-large' weight='bold'>MIDI Input</span>");
+        title.set_halign(gtk4::Align::Start);
+        vbox.append(&title);
+        
+        let subtitle = Label::new(Some("Every event is also passed to plugins as /midi/<channel>/cc/<number> or /midi/<channel>/note/<number> with a 0..1 float"));
+        subtitle.set_halign(gtk4::Align::Start);
+        subtitle.set_wrap(true);
+        vbox.append(&subtitle);
+        
+        let bridge = Arc::new(MidiBridge::new(osc_manager, app_state.console.clone(), app_state.config.clone()));
+        
+        // Port picker
+        let port_box = GtkBox::new(Orientation::Horizontal, 10);
+        let ports_model = StringList::new(&[]);
+        let port_dropdown = DropDown::new(Some(ports_model.clone()), None::<gtk4::Expression>);
+        port_dropdown.set_hexpand(true);
+        port_box.append(&port_dropdown);
+        let refresh_button = Button::with_label("Refresh");
+        port_box.append(&refresh_button);
+        let connect_button = Button::with_label("Connect");
+        port_box.append(&connect_button);
+        vbox.append(&port_box);
+        
+        let status_label = Label::new(None);
+        status_label.set_halign(gtk4::Align::Start);
+        status_label.set_wrap(true);
+        vbox.append(&status_label);
+        
+        let refresh_ports = {
+            let ports_model = ports_model.clone();
+            let status_label = status_label.clone();
+            move || match MidiBridge::list_ports() {
+                Ok(ports) => {
+                    let names: Vec<&str> = ports.iter().map(|p| p.as_str()).collect();
+                    ports_model.splice(0, ports_model.n_items(), &names);
+                    if ports.is_empty() {
+                        status_label.set_text("No MIDI input ports found");
+                    }
+                }
+                Err(e) => status_label.set_markup(&format!("<span foreground='red'>{}</span>", glib::markup_escape_text(&e.to_string()))),
+            }
+        };
+        refresh_ports();
+        refresh_button.connect_clicked(move |_| refresh_ports());
+        
+        // Reopen the port from last time
+        if let Some(port) = app_state.config.read().midi.port.clone() {
+            match bridge.connect(&port) {
+                Ok(()) => status_label.set_text(&format!("Connected to {}", port)),
+                Err(e) => app_state.console.write().log_error(&format!("Failed to open MIDI port: {}", e)),
+            }
+        }
+        
+        let app_state_clone = app_state.clone();
+        let status_clone = status_label.clone();
+        connect_button.connect_clicked(move |_| {
+            let port = match ports_model.string(port_dropdown.selected()) {
+                Some(port) => port.to_string(),
+                None => return,
+            };
+            
+            match bridge.connect(&port) {
+                Ok(()) => {
+                    status_clone.set_text(&format!("Connected to {}", port));
+                    let mut config = app_state_clone.config.write();
+                    config.midi.port = Some(port);
+                    if let Err(e) = config.save() {
+                        app_state_clone.console.write().log_error(&format!("Failed to save config: {}", e));
+                    }
+                }
+                Err(e) => status_clone.set_markup(&format!("<span foreground='red'>{}</span>", glib::markup_escape_text(&e.to_string()))),
+            }
+        });
+        
+        // Bindings: MIDI event -> OSC address
+        let bindings_title = Label::new(None);
+        bindings_title.set_markup("<b>Bindings</b>");
+        bindings_title.set_halign(gtk4::Align::Start);
+        bindings_title.set_margin_top(10);
+        vbox.append(&bindings_title);
+        
+        let bindings_list = GtkBox::new(Orientation::Vertical, 5);
+        Self::refresh_midi_bindings(&bindings_list, app_state.clone());
+        vbox.append(&bindings_list);
+        
+        let add_box = GtkBox::new(Orientation::Horizontal, 10);
+        let kind_dropdown = DropDown::from_strings(&["CC", "Note"]);
+        add_box.append(&kind_dropdown);
+        add_box.append(&Label::new(Some("Number")));
+        let number_spin = SpinButton::with_range(0.0, 127.0, 1.0);
+        add_box.append(&number_spin);
+        add_box.append(&Label::new(Some("Channel (0 = any)")));
+        let channel_spin = SpinButton::with_range(0.0, 16.0, 1.0);
+        add_box.append(&channel_spin);
+        let address_entry = Entry::new();
+        address_entry.set_placeholder_text(Some("/avatar/parameters/..."));
+        address_entry.set_hexpand(true);
+        add_box.append(&address_entry);
+        let add_button = Button::with_label("Add");
+        add_box.append(&add_button);
+        vbox.append(&add_box);
+        
+        let bindings_list_clone = bindings_list.clone();
+        add_button.connect_clicked(move |_| {
+            let address = address_entry.text().trim().to_string();
+            if !is_valid_osc_address(&address) {
+                address_entry.add_css_class("error");
+                return;
+            }
+            address_entry.remove_css_class("error");
+            
+            let kind = match kind_dropdown.selected() {
+                0 => MidiEventKind::ControlChange,
+                _ => MidiEventKind::Note,
+            };
+            let channel = match channel_spin.value_as_int() {
+                0 => None,
+                channel => Some(channel as u8),
+            };
+            
+            {
+                let mut config = app_state.config.write();
+                config.midi.bindings.push(MidiBinding {
+                    kind,
+                    channel,
+                    number: number_spin.value_as_int() as u8,
+                    address,
+                });
+                if let Err(e) = config.save() {
+                    app_state.console.write().log_error(&format!("Failed to save config: {}", e));
+                }
+            }
+            
+            address_entry.set_text("");
+            Self::refresh_midi_bindings(&bindings_list_clone, app_state.clone());
+        });
+        
+        vbox.upcast::<Widget>()
+    }
+    
+    // Rebuild the bindings list from the config
+    #[cfg(feature = "midi")]
+    fn refresh_midi_bindings(list: &GtkBox, app_state: Arc<AppState>) {
+        while let Some(child) = list.first_child() {
+            list.remove(&child);
+        }
+        
+        let bindings = app_state.config.read().midi.bindings.clone();
+        if bindings.is_empty() {
+            let empty_label = Label::new(Some("No bindings yet"));
+            empty_label.set_halign(gtk4::Align::Start);
+            list.append(&empty_label);
+            return;
+        }
+        
+        for (idx, binding) in bindings.iter().enumerate() {
+            let row = GtkBox::new(Orientation::Horizontal, 10);
+            
+            let kind = match binding.kind {
+                MidiEventKind::ControlChange => "CC",
+                MidiEventKind::Note => "Note",
+            };
+            let channel = binding.channel.map_or("any".to_string(), |c| c.to_string());
+            let label = Label::new(Some(&format!("{} {} (channel {}) \u{2192} {}", kind, binding.number, channel, binding.address)));
+            label.set_halign(gtk4::Align::Start);
+            label.set_hexpand(true);
+            row.append(&label);
+            
+            let remove_button = Button::with_label("Remove");
+            let list_clone = list.clone();
+            let app_state_clone = app_state.clone();
+            remove_button.connect_clicked(move |_| {
+                {
+                    let mut config = app_state_clone.config.write();
+                    if idx < config.midi.bindings.len() {
+                        config.midi.bindings.remove(idx);
+                    }
+                    if let Err(e) = config.save() {
+                        app_state_clone.console.write().log_error(&format!("Failed to save config: {}", e));
+                    }
+                }
+                Self::refresh_midi_bindings(&list_clone, app_state_clone.clone());
+            });
+            row.append(&remove_button);
+            
+            list.append(&row);
+        }
+    }
+    
+    #[cfg(feature = "gamepad")]
+    fn create_gamepad_tab(app_state: Arc<AppState>, osc_manager: Arc<OscManager>) -> Widget {
+        use crate::gamepad::{GamepadBridge, INPUTS};
+        
+        let vbox = GtkBox::new(Orientation::Vertical, 10);
+        vbox.set_margin_top(20);
+        vbox.set_margin_bottom(20);
+        vbox.set_margin_start(20);
+        vbox.set_margin_end(20);
+        
+        let title = Label::new(None);
+        title.set_markup("<span size='x-large' weight='bold'>Gamepad Input</span>");
+        title.set_halign(gtk4::Align::Start);
+        vbox.append(&title);
+        
+        let subtitle = Label::new(Some("Every change is also passed to plugins as /gamepad/axis/<name> (-1..1) or /gamepad/button/<name> (0..1)"));
+        subtitle.set_halign(gtk4::Align::Start);
+        subtitle.set_wrap(true);
+        vbox.append(&subtitle);
+        
+        let bridge = GamepadBridge::start(osc_manager, app_state.console.clone(), app_state.config.clone());
+        
+        // Connected controllers and the last input moved, so users can find the name to bind
+        let status_label = Label::new(Some("No controllers connected"));
+        status_label.set_halign(gtk4::Align::Start);
+        status_label.set_wrap(true);
+        vbox.append(&status_label);
+        
+        let status_clone = status_label.clone();
+        glib::timeout_add_local(Duration::from_millis(500), move || {
+            let gamepads = bridge.gamepads();
+            let mut text = if gamepads.is_empty() {
+                "No controllers connected".to_string()
+            } else {
+                format!("Connected: {}", gamepads.join(", "))
+            };
+            if let Some(input) = bridge.last_input() {
+                text.push_str(&format!(" · last input: {}", input));
+            }
+            status_clone.set_text(&text);
+            glib::ControlFlow::Continue
+        });
+        
+        // Bindings: controller input -> OSC address
+        let bindings_title = Label::new(None);
+        bindings_title.set_markup("<b>Bindings</b>");
+        bindings_title.set_halign(gtk4::Align::Start);
+        bindings_title.set_margin_top(10);
+        vbox.append(&bindings_title);
+        
+        let bindings_list = GtkBox::new(Orientation::Vertical, 5);
+        Self::refresh_gamepad_bindings(&bindings_list, app_state.clone());
+        vbox.append(&bindings_list);
+        
+        let add_box = GtkBox::new(Orientation::Horizontal, 10);
+        let input_dropdown = DropDown::from_strings(INPUTS);
+        add_box.append(&input_dropdown);
+        let address_entry = Entry::new();
+        address_entry.set_placeholder_text(Some("/avatar/parameters/..."));
+        address_entry.set_hexpand(true);
+        add_box.append(&address_entry);
+        let add_button = Button::with_label("Add");
+        add_box.append(&add_button);
+        vbox.append(&add_box);
+        
+        let bindings_list_clone = bindings_list.clone();
+        add_button.connect_clicked(move |_| {
+            let address = address_entry.text().trim().to_string();
+            if !is_valid_osc_address(&address) {
+                address_entry.add_css_class("error");
+                return;
+            }
+            address_entry.remove_css_class("error");
+            
+            let input = match INPUTS.get(input_dropdown.selected() as usize) {
+                Some(input) => input.to_string(),
+                None => return,
+            };
+            
+            {
+                let mut config = app_state.config.write();
+                config.gamepad.bindings.push(GamepadBinding { input, address });
+                if let Err(e) = config.save() {
+                    app_state.console.write().log_error(&format!("Failed to save config: {}", e));
+                }
+            }
+            
+            address_entry.set_text("");
+            Self::refresh_gamepad_bindings(&bindings_list_clone, app_state.clone());
+        });
+        
+        vbox.upcast::<Widget>()
+    }
+    
+    // Rebuild the bindings list from the config
+    #[cfg(feature = "gamepad")]
+    fn refresh_gamepad_bindings(list: &GtkBox, app_state: Arc<AppState>) {
+        while let Some(child) = list.first_child() {
+            list.remove(&child);
+        }
+        
+        let bindings = app_state.config.read().gamepad.bindings.clone();
+        if bindings.is_empty() {
+            let empty_label = Label::new(Some("No bindings yet"));
+            empty_label.set_halign(gtk4::Align::Start);
+            list.append(&empty_label);
+            return;
+        }
+        
+        for (idx, binding) in bindings.iter().enumerate() {
+            let row = GtkBox::new(Orientation::Horizontal, 10);
+            
+            let label = Label::new(Some(&format!("{} \u{2192} {}", binding.input, binding.address)));
+            label.set_halign(gtk4::Align::Start);
+            label.set_hexpand(true);
+            row.append(&label);
+            
+            let remove_button = Button::with_label("Remove");
+            let list_clone = list.clone();
+            let app_state_clone = app_state.clone();
+            remove_button.connect_clicked(move |_| {
+                {
+                    let mut config = app_state_clone.config.write();
+                    if idx < config.gamepad.bindings.len() {
+                        config.gamepad.bindings.remove(idx);
+                    }
+                    if let Err(e) = config.save() {
+                        app_state_clone.console.write().log_error(&format!("Failed to save config: {}", e));
+                    }
+                }
+                Self::refresh_gamepad_bindings(&list_clone, app_state_clone.clone());
+            });
+            row.append(&remove_button);
+            
+            list.append(&row);
+        }
+    }
     
     fn create_plugins_tab(app_state: Arc<AppState>) -> Widget {
         let vbox = GtkBox::new(Orientation::Vertical, 10);
@@ -237,6 +1231,61 @@ impl MainWindow {
         subtitle.set_wrap(true);
         vbox.append(&subtitle);
         
+        // Update interval; the running timer picks up changes on its next tick
+        let interval_box = GtkBox::new(Orientation::Horizontal, 10);
+        interval_box.set_margin_top(10);
+        let interval_label = Label::new(Some("Update interval (ms):"));
+        interval_label.set_width_chars(15);
+        interval_label.set_halign(gtk4::Align::Start);
+        interval_box.append(&interval_label);
+        
+        let interval_spin = SpinButton::with_range(10.0, 1000.0, 10.0);
+        interval_spin.set_value(app_state.config.read().tick_interval_ms as f64);
+        let app_state_clone = app_state.clone();
+        interval_spin.connect_value_changed(move |spin| {
+            let mut config = app_state_clone.config.write();
+            config.tick_interval_ms = spin.value() as u64;
+            if let Err(e) = config.save() {
+                app_state_clone.console.write().log_error(&format!("Failed to save config: {}", e));
+            }
+        });
+        interval_box.append(&interval_spin);
+        vbox.append(&interval_box);
+        
+        // Profiles: named sets of plugin enable states and settings. Handlers are
+        // connected below, once the plugin switches they have to update exist.
+        let profile_box = GtkBox::new(Orientation::Horizontal, 10);
+        let profile_label = Label::new(Some("Profile:"));
+        profile_label.set_width_chars(15);
+        profile_label.set_halign(gtk4::Align::Start);
+        profile_box.append(&profile_label);
+        
+        let profiles_model = StringList::new(&[]);
+        let profile_dropdown = DropDown::new(Some(profiles_model.clone()), None::<gtk4::Expression>);
+        profile_box.append(&profile_dropdown);
+        
+        let delete_profile_button = Button::with_label("Delete");
+        profile_box.append(&delete_profile_button);
+        
+        let new_profile_entry = Entry::new();
+        new_profile_entry.set_placeholder_text(Some("New profile name"));
+        new_profile_entry.set_hexpand(true);
+        profile_box.append(&new_profile_entry);
+        
+        let new_profile_button = Button::with_label("Save as New");
+        profile_box.append(&new_profile_button);
+        vbox.append(&profile_box);
+        
+        let profile_error_label = Label::new(None);
+        profile_error_label.set_halign(gtk4::Align::Start);
+        profile_error_label.set_wrap(true);
+        vbox.append(&profile_error_label);
+        
+        // Set while the dropdown is being refilled, so the selection changes that causes
+        // aren't taken as the user picking a profile
+        let profiles_updating = Rc::new(Cell::new(false));
+        Self::refresh_profiles(&profiles_model, &profile_dropdown, &profiles_updating, &app_state);
+        
         // Separator
         let separator = gtk4::Separator::new(Orientation::Horizontal);
         separator.set_margin_top(10);
@@ -245,12 +1294,29 @@ impl MainWindow {
         
         let plugin_loader = app_state.plugin_loader.read();
         let plugins = plugin_loader.plugins();
+        let mut plugin_switches = Vec::new();
+        
+        if let Some(error) = plugin_loader.dir_error() {
+            let dir_error_label = Label::new(None);
+            dir_error_label.set_markup(&format!("<span foreground='red'>{}</span>", glib::markup_escape_text(error)));
+            dir_error_label.set_halign(gtk4::Align::Start);
+            dir_error_label.set_wrap(true);
+            vbox.append(&dir_error_label);
+        }
         
         if plugins.is_empty() {
             let empty_label = Label::new(Some("No plugins loaded. Place .wasm files in ~/.config/fox-osc/plugins/"));
             empty_label.set_halign(gtk4::Align::Start);
             vbox.append(&empty_label);
         } else {
+            let mut status_labels = Vec::new();
+            
+            // Filter cards by name or description
+            let search_entry = Entry::new();
+            search_entry.set_placeholder_text(Some("Search plugins..."));
+            vbox.append(&search_entry);
+            let mut cards = Vec::new();
+            
             for (idx, plugin) in plugins.iter().enumerate() {
                 let plugin_box = GtkBox::new(Orientation::Horizontal, 10);
                 plugin_box.set_margin_top(10);
@@ -264,16 +1330,87 @@ impl MainWindow {
                 // Plugin name and version
                 let name_label = Label::new(None);
                 name_label.set_markup(&format!("<span size='large' weight='bold'>{}</span> <span size='small'>v{}</span>", 
-                    info.name, info.version));
+                    glib::markup_escape_text(plugin.name()), info.version));
                 name_label.set_halign(gtk4::Align::Start);
                 info_vbox.append(&name_label);
                 
+                // Still loaded, but flag plugins built against an older host ABI
+                if let Compat::OlderAbi(version) = plugin.compatibility() {
+                    let compat_label = Label::new(None);
+                    compat_label.set_markup(&format!(
+                        "<span foreground='orange'>\u{26a0} Built for older API (v{}, host is v{}). It may misbehave until rebuilt.</span>",
+                        version, HOST_ABI_VERSION
+                    ));
+                    compat_label.set_halign(gtk4::Align::Start);
+                    compat_label.set_wrap(true);
+                    info_vbox.append(&compat_label);
+                }
+                
                 // Description
                 let desc_label = Label::new(Some(&info.description));
                 desc_label.set_halign(gtk4::Align::Start);
                 desc_label.set_wrap(true);
                 info_vbox.append(&desc_label);
                 
+                // Capability badges
+                if !info.capabilities.is_empty() {
+                    let badges_box = GtkBox::new(Orientation::Horizontal, 5);
+                    for capability in &info.capabilities {
+                        let badge = Label::new(None);
+                        badge.set_markup(&format!(
+                            "<span size='small' background='#3c6e9f' foreground='white'> {} </span>",
+                            glib::markup_escape_text(capability)
+                        ));
+                        badges_box.append(&badge);
+                    }
+                    info_vbox.append(&badges_box);
+                }
+                
+                // Declared permissions
+                let permissions_text = if info.permissions.is_empty() {
+                    "Permissions: none".to_string()
+                } else {
+                    let descriptions: Vec<&str> = info.permissions.iter().map(|p| p.description()).collect();
+                    format!("Permissions: {}", descriptions.join(", "))
+                };
+                let permissions_label = Label::new(Some(&permissions_text));
+                permissions_label.set_halign(gtk4::Align::Start);
+                permissions_label.set_wrap(true);
+                info_vbox.append(&permissions_label);
+                
+                // Live status, refreshed by the timer below
+                let status_label = Label::new(None);
+                status_label.set_halign(gtk4::Align::Start);
+                status_label.set_wrap(true);
+                if let Some(status) = plugin.status() {
+                    status_label.set_markup(&format!("<i>{}</i>", glib::markup_escape_text(&status)));
+                }
+                info_vbox.append(&status_label);
+                
+                // Cost per update tick and memory use, refreshed by the same timer
+                let metrics_label = Label::new(None);
+                metrics_label.set_halign(gtk4::Align::Start);
+                metrics_label.set_markup(&Self::usage_markup(plugin.metrics(), plugin.memory_size()));
+                info_vbox.append(&metrics_label);
+                
+                // Why the plugin last failed, if it did; cleared once it runs again
+                let error_label = Label::new(None);
+                error_label.set_halign(gtk4::Align::Start);
+                error_label.set_wrap(true);
+                Self::show_plugin_error(&error_label, plugin.last_error());
+                info_vbox.append(&error_label);
+                status_labels.push((idx, status_label, metrics_label, error_label.clone()));
+                
+                // Which hooks the host found, since a misnamed export just never gets called
+                let exports_expander = gtk4::Expander::new(Some("Exported functions"));
+                let exports_label = Label::new(None);
+                exports_label.set_markup(&Self::exports_markup(&plugin.exported_functions()));
+                exports_label.set_halign(gtk4::Align::Start);
+                exports_label.set_wrap(true);
+                exports_label.set_selectable(true);
+                exports_expander.set_child(Some(&exports_label));
+                info_vbox.append(&exports_expander);
+                
                 // UI config available?
                 if plugin.ui_config().is_some() {
                     let ui_label = Label::new(Some("\u{2699} Has configuration tab"));
@@ -283,26 +1420,34 @@ impl MainWindow {
                 
                 plugin_box.append(&info_vbox);
                 
-                // Right side - on/off switch
+                // Right side - on/off switch. Off only pauses, so the plugin keeps its
+                // in-memory state; Stop is the full reset.
                 let switch = Switch::new();
-                switch.set_active(plugin.is_running());
+                switch.set_active(plugin.is_running() && !plugin.is_paused());
                 switch.set_valign(gtk4::Align::Center);
                 switch.set_margin_start(20);
+                switch.set_tooltip_text(Some("Pause or resume the plugin, keeping its state"));
+                
+                let stop_button = Button::with_label("Stop");
+                stop_button.set_valign(gtk4::Align::Center);
+                stop_button.set_tooltip_text(Some("Stop the plugin; it starts fresh when switched back on"));
                 
                 let app_state_clone = app_state.clone();
+                let error_label_clone = error_label.clone();
                 switch.connect_state_set(move |_, enabled| {
                     let mut loader = app_state_clone.plugin_loader.write();
                     if let Some(plugin) = loader.plugins_mut().get_mut(idx) {
-                        let plugin_name = plugin.info().name.clone();
+                        let plugin_name = plugin.name().to_string();
                         
                         let result = if enabled {
                             plugin.start()
                         } else {
-                            plugin.stop()
+                            plugin.pause()
                         };
+                        Self::show_plugin_error(&error_label_clone, plugin.last_error());
                         
                         if let Err(e) = result {
-                            let action = if enabled { "start" } else { "stop" };
+                            let action = if enabled { "start" } else { "pause" };
                             app_state_clone.console.write().log_error(
                                 &format!("Failed to {} plugin: {}", action, e)
                             );
@@ -318,7 +1463,27 @@ impl MainWindow {
                     glib::Propagation::Proceed
                 });
                 
+                let app_state_clone = app_state.clone();
+                let switch_clone = switch.clone();
+                stop_button.connect_clicked(move |_| {
+                    let mut loader = app_state_clone.plugin_loader.write();
+                    let plugin = match loader.plugins_mut().get_mut(idx) {
+                        Some(plugin) => plugin,
+                        None => return,
+                    };
+                    if let Err(e) = plugin.stop() {
+                        app_state_clone.console.write().log_error(&format!("Failed to stop plugin: {}", e));
+                    }
+                    Self::show_plugin_error(&error_label, plugin.last_error());
+                    drop(loader);
+                    
+                    // Saves the disabled state; pausing a stopped plugin is a no-op
+                    switch_clone.set_active(false);
+                });
+                
                 plugin_box.append(&switch);
+                plugin_box.append(&stop_button);
+                plugin_switches.push((idx, switch));
                 
                 vbox.append(&plugin_box);
                 
@@ -326,9 +1491,122 @@ impl MainWindow {
                 let separator = gtk4::Separator::new(Orientation::Horizontal);
                 separator.set_margin_top(5);
                 vbox.append(&separator);
+                
+                let search_text = format!("{} {}", plugin.name(), info.description).to_lowercase();
+                cards.push((plugin_box, separator, search_text));
             }
+            
+            search_entry.connect_changed(move |entry| {
+                let query = entry.text().to_lowercase();
+                for (card, separator, search_text) in &cards {
+                    let visible = search_text.contains(query.trim());
+                    card.set_visible(visible);
+                    separator.set_visible(visible);
+                }
+            });
+            
+            // Poll plugin_status and metrics slowly; they're only meant for a human to glance at
+            let app_state_timer = app_state.clone();
+            glib::timeout_add_seconds_local(3, move || {
+                let loader = app_state_timer.plugin_loader.read();
+                for (idx, status_label, metrics_label, error_label) in &status_labels {
+                    let plugin = match loader.plugins().get(*idx) {
+                        Some(plugin) => plugin,
+                        None => continue,
+                    };
+                    match plugin.status() {
+                        Some(status) => status_label.set_markup(&format!("<i>{}</i>", glib::markup_escape_text(&status))),
+                        None => status_label.set_text(""),
+                    }
+                    metrics_label.set_markup(&Self::usage_markup(plugin.metrics(), plugin.memory_size()));
+                    Self::show_plugin_error(error_label, plugin.last_error());
+                }
+                glib::ControlFlow::Continue
+            });
         }
         drop(plugin_loader);
+        let plugin_switches = Rc::new(plugin_switches);
+        
+        let app_state_clone = app_state.clone();
+        let profiles_model_clone = profiles_model.clone();
+        let profiles_updating_clone = profiles_updating.clone();
+        let plugin_switches_clone = plugin_switches.clone();
+        let error_label_clone = profile_error_label.clone();
+        profile_dropdown.connect_selected_notify(move |dropdown| {
+            if profiles_updating_clone.get() {
+                return;
+            }
+            let name = match profiles_model_clone.string(dropdown.selected()) {
+                Some(name) => name.to_string(),
+                None => return,
+            };
+            
+            let result = app_state_clone.config.write().switch_profile(&name);
+            match result {
+                Ok(()) => {
+                    error_label_clone.set_text("");
+                    Self::apply_profile(&app_state_clone, &plugin_switches_clone);
+                }
+                Err(e) => error_label_clone.set_markup(&format!(
+                    "<span foreground='red'>{}</span>", glib::markup_escape_text(&e.to_string())
+                )),
+            }
+        });
+        
+        let app_state_clone = app_state.clone();
+        let profiles_model_clone = profiles_model.clone();
+        let profile_dropdown_clone = profile_dropdown.clone();
+        let profiles_updating_clone = profiles_updating.clone();
+        let error_label_clone = profile_error_label.clone();
+        new_profile_button.connect_clicked(move |_| {
+            let name = new_profile_entry.text().trim().to_string();
+            let result = app_state_clone.config.write().create_profile(&name);
+            match result {
+                Ok(()) => {
+                    error_label_clone.set_text("");
+                    new_profile_entry.set_text("");
+                    Self::save_config(&app_state_clone);
+                    app_state_clone.console.write().log_info(&format!("Created profile '{}'", name));
+                    Self::refresh_profiles(&profiles_model_clone, &profile_dropdown_clone, &profiles_updating_clone, &app_state_clone);
+                }
+                Err(e) => error_label_clone.set_markup(&format!(
+                    "<span foreground='red'>{}</span>", glib::markup_escape_text(&e.to_string())
+                )),
+            }
+        });
+        
+        let app_state_clone = app_state.clone();
+        delete_profile_button.connect_clicked(move |button| {
+            let name = app_state_clone.config.read().active_profile.clone();
+            if name == DEFAULT_PROFILE {
+                profile_error_label.set_markup("<span foreground='red'>The default profile can't be deleted</span>");
+                return;
+            }
+            
+            let app_state_dialog = app_state_clone.clone();
+            let profiles_model = profiles_model.clone();
+            let profile_dropdown = profile_dropdown.clone();
+            let profiles_updating = profiles_updating.clone();
+            let plugin_switches = plugin_switches.clone();
+            let error_label = profile_error_label.clone();
+            Self::confirm(
+                button,
+                "Delete profile",
+                &format!("Delete profile '{}'? Its plugin settings are lost and the default profile is loaded.", name),
+                "Delete",
+                move || {
+                    let result = app_state_dialog.config.write().delete_profile(&name);
+                    if let Err(e) = result {
+                        error_label.set_markup(&format!("<span foreground='red'>{}</span>", glib::markup_escape_text(&e.to_string())));
+                        return;
+                    }
+                    error_label.set_text("");
+                    app_state_dialog.console.write().log_info(&format!("Deleted profile '{}'", name));
+                    Self::refresh_profiles(&profiles_model, &profile_dropdown, &profiles_updating, &app_state_dialog);
+                    Self::apply_profile(&app_state_dialog, &plugin_switches);
+                },
+            );
+        });
         
         // Info about adding plugins
         let info_box = GtkBox::new(Orientation::Vertical, 5);
@@ -339,7 +1617,7 @@ impl MainWindow {
         info_title.set_halign(gtk4::Align::Start);
         info_box.append(&info_title);
         
-        let info_text = Label::new(Some("1. Place .wasm files in ~/.config/fox-osc/plugins/\n2. Restart the application\n3. Plugins will load automatically"));
+        let info_text = Label::new(Some("1. Place .wasm files (optionally .wasm.gz or .wasm.zst) in ~/.config/fox-osc/plugins/\n2. Restart the application\n3. Plugins will load automatically"));
         info_text.set_halign(gtk4::Align::Start);
         info_box.append(&info_text);
         
@@ -348,6 +1626,81 @@ impl MainWindow {
         vbox.upcast::<Widget>()
     }
     
+    // Known hooks as present or missing (missing required ones in red), then any other exports
+    fn exports_markup(exports: &[String]) -> String {
+        let mut lines: Vec<String> = PLUGIN_HOOKS
+            .iter()
+            .map(|(hook, required)| {
+                match (exports.iter().any(|e| e == hook), required) {
+                    (true, _) => format!("\u{2714} {}", hook),
+                    (false, true) => format!("<span foreground='red'>\u{2717} {} (required)</span>", hook),
+                    (false, false) => format!("<span foreground='gray'>\u{2717} {}</span>", hook),
+                }
+            })
+            .collect();
+        
+        let others: Vec<&str> = exports
+            .iter()
+            .map(|e| e.as_str())
+            .filter(|e| !PLUGIN_HOOKS.iter().any(|(hook, _)| hook == e))
+            .collect();
+        if !others.is_empty() {
+            lines.push(format!("Other: {}", glib::markup_escape_text(&others.join(", "))));
+        }
+        
+        format!("<span size='small'>{}</span>", lines.join("\n"))
+    }
+    
+    // Refill the profile dropdown from the config and select the active profile
+    fn refresh_profiles(model: &StringList, dropdown: &DropDown, updating: &Cell<bool>, app_state: &AppState) {
+        let config = app_state.config.read();
+        let names = config.profile_names();
+        let active = names.iter().position(|name| *name == config.active_profile).unwrap_or(0);
+        drop(config);
+        
+        updating.set(true);
+        let names: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+        model.splice(0, model.n_items(), &names);
+        dropdown.set_selected(active as u32);
+        updating.set(false);
+    }
+    
+    // Start or stop every plugin to match the newly active profile, have running ones
+    // reload their settings, and save. Plugin tabs re-read their inputs when next shown.
+    fn apply_profile(app_state: &AppState, plugin_switches: &[(usize, Switch)]) {
+        let mut loader = app_state.plugin_loader.write();
+        for plugin in loader.plugins_mut() {
+            let enabled = app_state.config.read().plugin_enabled(plugin.name());
+            let result = if enabled {
+                plugin.start().and_then(|_| plugin.load_config_from_disk())
+            } else {
+                plugin.stop()
+            };
+            if let Err(e) = result {
+                app_state.console.write().log_error(&format!("Failed to apply profile to {}: {}", plugin.name(), e));
+            }
+        }
+        let running: Vec<bool> = loader.plugins().iter().map(|plugin| plugin.is_running() && !plugin.is_paused()).collect();
+        drop(loader);
+        
+        // The switch handlers start/pause plugins too, which is a no-op by now
+        for (idx, switch) in plugin_switches {
+            if let Some(&running) = running.get(*idx) {
+                switch.set_active(running);
+            }
+        }
+        
+        let profile = app_state.config.read().active_profile.clone();
+        app_state.console.write().log_info(&format!("Switched to profile '{}'", profile));
+        Self::save_config(app_state);
+    }
+    
+    fn save_config(app_state: &AppState) {
+        if let Err(e) = app_state.config.read().save() {
+            app_state.console.write().log_error(&format!("Failed to save config: {}", e));
+        }
+    }
+    
     pub fn window(&self) -> &ApplicationWindow {
         &self.window
     }
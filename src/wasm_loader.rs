@@ -1,47 +1,209 @@
 use anyhow::{Context, Result};
 use wasmtime::*;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use std::io::Read;
 use parking_lot::{RwLock, Mutex};
 use std::fs;
-use chrono::{Local, Timelike};
+use chrono::{Local, Timelike, Utc};
 use rosc::OscType;
+use rand::Rng;
 
-use crate::plugin_api::{PluginInfo, UiConfig};
+use crate::plugin_api::{is_valid_osc_address, normalized_value, BundleEntry, OscValue, Permission, OSC_TAG_BOOL, OSC_TAG_COLOR, OSC_TAG_FLOAT, OSC_TAG_INT, PluginInfo, UiConfig, UiEvent, HOST_ABI_VERSION, MIN_PLUGIN_ABI_VERSION};
 use crate::console::ConsoleLog;
-use crate::osc_manager::OscManager;
-use crate::config::Config;
+use crate::osc_manager::{OscManager, OscError};
+use crate::config::{app_dir, Config};
+
+// Reference point for get_monotonic_millis, pinned when the plugin loader is created
+static APP_START: OnceLock<Instant> = OnceLock::new();
+
+// Limits for the http_get host function
+const HTTP_TIMEOUT: Duration = Duration::from_secs(5);
+const HTTP_MAX_RESPONSE_BYTES: u64 = 256 * 1024;
+
+// Messages waiting for delivery between plugins; anything past this is dropped
+const MAX_QUEUED_MESSAGES: usize = 256;
+
+// Where plugin_on_message's sender name and payload are written in plugin memory
+const MESSAGE_WRITE_POS: usize = 4096;
+
+// Where plugin_on_osc's address is written; only valid during the call, like messages
+const OSC_ADDRESS_WRITE_POS: usize = MESSAGE_WRITE_POS;
+
+// Received OSC values waiting for a plugin's next tick; past this the oldest are dropped
+const MAX_PENDING_OSC: usize = 1024;
+
+// Plugin file endings: plain, gzip-compressed and zstd-compressed modules
+const PLUGIN_EXTENSIONS: [&str; 3] = [".wasm", ".wasm.gz", ".wasm.zst"];
+
+// Compressed plugins are unpacked in memory; a corrupt or hostile file stops here
+const MAX_DECOMPRESSED_PLUGIN_BYTES: u64 = 256 * 1024 * 1024;
+
+// Minimum time between desktop notifications from one plugin
+const NOTIFY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Time spent in a plugin's plugin_update calls
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PluginMetrics {
+    pub ticks: u64,
+    pub total: Duration,
+    /// Exponential moving average over roughly the last 20 ticks
+    pub avg_us: f64,
+}
+
+impl PluginMetrics {
+    fn record(&mut self, elapsed: Duration) {
+        let us = elapsed.as_secs_f64() * 1_000_000.0;
+        self.avg_us = if self.ticks == 0 { us } else { self.avg_us * 0.95 + us * 0.05 };
+        self.ticks += 1;
+        self.total += elapsed;
+    }
+}
 
 pub struct WasmPlugin {
+    // Key for config, data dir and messages: info.name, plus the instance if there is one
     name: String,
+    instance_id: Option<String>,
     instance: Arc<Mutex<Instance>>,
     store: Arc<Mutex<Store<PluginState>>>,
     info: PluginInfo,
     ui_config: Option<UiConfig>,
     running: Arc<RwLock<bool>>,
+    // Running but skipping updates, OSC and messages until resumed; see `pause`
+    paused: Arc<RwLock<bool>>,
     app_config: Arc<RwLock<Config>>,
+    last_update: Option<Instant>,
+    status_fn: Option<TypedFunc<(), i32>>,
+    osc_inbox: Arc<Mutex<OscInbox>>,
+    metrics: PluginMetrics,
+    // Most recent start/stop/update failure, cleared by the next success
+    last_error: Option<String>,
+    compatibility: Compat,
+}
+
+/// How a loaded plugin's ABI version compares to the host's
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compat {
+    /// Built against `HOST_ABI_VERSION`
+    Current,
+    /// Built against an older ABI the host still accepts; it may rely on behaviour
+    /// that has since changed
+    OlderAbi(u32),
 }
 
+/// Functions the host calls on a plugin if it exports them, and whether the plugin
+/// can't work without it. A misspelled export is silently never called, so the
+/// Plugins tab lists which of these each plugin has.
+pub const PLUGIN_HOOKS: &[(&str, bool)] = &[
+    ("plugin_info", true),
+    ("plugin_start", true),
+    ("plugin_stop", true),
+    ("plugin_pause", false),
+    ("plugin_resume", false),
+    ("plugin_abi_version", false),
+    ("plugin_update", false),
+    ("plugin_status", false),
+    ("plugin_ui_config", false),
+    ("plugin_ui_event", false),
+    ("plugin_load_config", false),
+    ("plugin_on_osc", false),
+    ("plugin_on_osc_float", false),
+    ("plugin_on_osc_int", false),
+    ("plugin_on_osc_bool", false),
+    ("plugin_on_osc_color", false),
+    ("plugin_on_timer", false),
+    ("plugin_on_message", false),
+    ("plugin_has_unsaved_state", false),
+];
+
 #[derive(Clone)]
 pub struct PluginState {
     pub osc_manager: Arc<OscManager>,
     pub console: Arc<RwLock<ConsoleLog>>,
     pub app_config: Arc<RwLock<Config>>,
     pub plugin_name: String,
+    pub data_dir: PathBuf,
+    pub permissions: Vec<Permission>,
+    pub timers: HashMap<i32, PluginTimer>,
+    pub last_notification: Option<Instant>,
+    pub messages: MessageQueue,
+    /// When plugin_start last succeeded; None while stopped
+    pub started_at: Option<Instant>,
+    pub ui_values: UiValues,
+}
+
+/// Message from one plugin to another, queued by send_plugin_message
+#[derive(Clone, Debug)]
+pub struct PluginMessage {
+    pub from: String,
+    pub to: String,
+    pub data: Vec<u8>,
+}
+
+/// Shared between all plugins; drained by the loader once per tick
+pub type MessageQueue = Arc<Mutex<Vec<PluginMessage>>>;
+
+/// OSC values received for a plugin but not yet handed to it. Filled on the OSC
+/// receive thread, drained on the tick, so the receiver never waits on a plugin.
+#[derive(Default)]
+struct OscInbox {
+    values: VecDeque<(String, OscType)>,
+    // Dropped because the inbox was full, reported on the next drain
+    dropped: usize,
+}
+
+/// Values a plugin pushes with ui_set_value, keyed by UI element id. The plugin's
+/// config tab polls these to drive live widgets like progress bars.
+pub type UiValues = Arc<Mutex<HashMap<String, f32>>>;
+
+/// Repeating timer registered with set_timer; fires plugin_on_timer(id)
+#[derive(Clone, Debug)]
+pub struct PluginTimer {
+    pub interval: Duration,
+    pub next_fire: Instant,
+}
+
+// A compiled plugin whose info has been read, but that hasn't been instantiated for real.
+// load_all prepares every plugin first so it can order them by `depends_on` before any
+// of them is set up.
+struct PreparedPlugin {
+    module: Module,
+    linker: Linker<PluginState>,
+    // Only used to read the info; its state carries the shared handles over to the real store
+    temp_store: Store<PluginState>,
+    name: String,
+    instance_id: Option<String>,
+    info: PluginInfo,
+    compatibility: Compat,
 }
 
 impl WasmPlugin {
     pub fn new(
         path: &Path,
+        data_root: &Path,
         osc_manager: Arc<OscManager>,
         console: Arc<RwLock<ConsoleLog>>,
         app_config: Arc<RwLock<Config>>,
+        messages: MessageQueue,
     ) -> Result<Self> {
+        Self::instantiate(Self::prepare(path, data_root, osc_manager, console, app_config, messages)?)
+    }
+    
+    fn prepare(
+        path: &Path,
+        data_root: &Path,
+        osc_manager: Arc<OscManager>,
+        console: Arc<RwLock<ConsoleLog>>,
+        app_config: Arc<RwLock<Config>>,
+        messages: MessageQueue,
+    ) -> Result<PreparedPlugin> {
         // Create WASM engine
         let engine = Engine::default();
         
         // Read WASM module
-        let module = Module::from_file(&engine, path)
+        let module = Self::read_module(&engine, path)
             .context("Failed to load WASM module")?;
         
         // Create linker with host functions
@@ -52,26 +214,63 @@ impl WasmPlugin {
         
         // Get plugin info first (need it for state)
         let mut temp_store = Store::new(&engine, PluginState {
-            osc_manager: osc_manager.clone(),
+            osc_manager,
             console: console.clone(),
-            app_config: app_config.clone(),
+            app_config,
             plugin_name: "temp".to_string(),
+            data_dir: data_root.to_path_buf(),
+            permissions: Vec::new(),
+            timers: HashMap::new(),
+            last_notification: None,
+            messages,
+            started_at: None,
+            ui_values: UiValues::default(),
         });
         
         let temp_instance = linker.instantiate(&mut temp_store, &module)
             .context("Failed to instantiate WASM module")?;
         
+        // Refuse plugins built against a host interface we no longer provide, and
+        // remember if it's one we still support but have moved on from
+        let compatibility = Self::check_abi_version(&temp_instance, &mut temp_store)?;
+        if let Compat::OlderAbi(version) = compatibility {
+            console.write().log_error(&format!(
+                "{} was built for host ABI v{} (current is v{}); it may misbehave until rebuilt",
+                path.display(), version, HOST_ABI_VERSION
+            ));
+        }
+        
         let info = Self::call_get_info(&temp_instance, &mut temp_store)?;
-        let name = info.name.clone();
+        let instance_id = Self::instance_id_for(path);
+        let name = match &instance_id {
+            Some(id) => format!("{} ({})", info.name, id),
+            None => info.name.clone(),
+        };
+        
+        Ok(PreparedPlugin { module, linker, temp_store, name, instance_id, info, compatibility })
+    }
+    
+    fn instantiate(prepared: PreparedPlugin) -> Result<Self> {
+        let PreparedPlugin { module, linker, temp_store, name, instance_id, info, compatibility } = prepared;
         
         // Now create proper store with correct plugin name
+        let temp = temp_store.into_data();
+        let console = temp.console.clone();
+        let app_config = temp.app_config.clone();
         let state = PluginState {
-            osc_manager: osc_manager.clone(),
-            console: console.clone(),
-            app_config: app_config.clone(),
+            osc_manager: temp.osc_manager,
+            console: temp.console,
+            app_config: temp.app_config,
             plugin_name: name.clone(),
+            data_dir: temp.data_dir.join(Self::dir_name_for(&name)),
+            permissions: info.permissions.clone(),
+            timers: HashMap::new(),
+            last_notification: None,
+            messages: temp.messages,
+            started_at: None,
+            ui_values: UiValues::default(),
         };
-        let mut store = Store::new(&engine, state);
+        let mut store = Store::new(module.engine(), state);
         
         // Instantiate again with proper state
         let instance = linker.instantiate(&mut store, &module)
@@ -80,32 +279,152 @@ impl WasmPlugin {
         // Try to get UI config
         let ui_config = Self::call_get_ui_config(&instance, &mut store).ok();
         
+        // Optional live status line, polled by the Plugins tab
+        let status_fn = instance.get_typed_func::<(), i32>(&mut store, "plugin_status").ok();
+        
         console.write().log_info(&format!("Loaded plugin: {} v{}", info.name, info.version));
         
         Ok(Self {
             name,
+            instance_id,
             instance: Arc::new(Mutex::new(instance)),
             store: Arc::new(Mutex::new(store)),
             info,
             ui_config,
             running: Arc::new(RwLock::new(false)),
+            paused: Arc::new(RwLock::new(false)),
             app_config,
+            last_update: None,
+            status_fn,
+            osc_inbox: Arc::new(Mutex::new(OscInbox::default())),
+            metrics: PluginMetrics::default(),
+            last_error: None,
+            compatibility,
         })
     }
     
+    // .wasm.gz and .wasm.zst files are decompressed first; anything else is read as-is
+    fn read_module(engine: &Engine, path: &Path) -> Result<Module> {
+        let file_name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+        let decoder: Box<dyn Read> = if file_name.ends_with(".wasm.gz") {
+            Box::new(flate2::read::GzDecoder::new(fs::File::open(path)?))
+        } else if file_name.ends_with(".wasm.zst") {
+            Box::new(zstd::stream::read::Decoder::new(fs::File::open(path)?)?)
+        } else {
+            return Module::from_file(engine, path);
+        };
+        
+        let mut bytes = Vec::new();
+        decoder.take(MAX_DECOMPRESSED_PLUGIN_BYTES + 1).read_to_end(&mut bytes)
+            .context("Failed to decompress plugin")?;
+        if bytes.len() as u64 > MAX_DECOMPRESSED_PLUGIN_BYTES {
+            anyhow::bail!("Plugin decompresses to more than {} MiB", MAX_DECOMPRESSED_PLUGIN_BYTES / (1024 * 1024));
+        }
+        Module::from_binary(engine, &bytes)
+    }
+    
+    // File name without its plugin extension, or None if it isn't a plugin file
+    fn plugin_file_stem(path: &Path) -> Option<&str> {
+        let file_name = path.file_name()?.to_str()?;
+        PLUGIN_EXTENSIONS.iter().find_map(|ext| file_name.strip_suffix(ext))
+    }
+    
+    // `watch@tokyo.wasm` is the "tokyo" instance of whatever plugin it contains, so one
+    // build can be copied and configured several times
+    fn instance_id_for(path: &Path) -> Option<String> {
+        let stem = Self::plugin_file_stem(path)?;
+        let (_, id) = stem.split_once('@')?;
+        let id = id.trim();
+        (!id.is_empty()).then(|| id.to_string())
+    }
+    
+    // Plugin names are free text, keep only characters that are safe in a directory name
+    fn dir_name_for(plugin_name: &str) -> String {
+        plugin_name
+            .chars()
+            .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' { c } else { '_' })
+            .collect()
+    }
+    
+    // Resolve a plugin-supplied file name inside its data dir. Only plain file names
+    // are allowed: no separators, no "..", nothing absolute.
+    fn sandboxed_path(data_dir: &Path, name: &str) -> Option<PathBuf> {
+        let invalid = name.is_empty()
+            || name == "."
+            || name == ".."
+            || name.contains(['/', '\\', ':'])
+            || Path::new(name).is_absolute();
+        
+        if invalid {
+            None
+        } else {
+            Some(data_dir.join(name))
+        }
+    }
+    
+    // Store a plugin setting and persist the config, as save_config does
+    fn store_plugin_setting(state: &PluginState, key: &str, value: &str) {
+        // Written by the update loop shortly after, or by save_config_now
+        let mut config = state.app_config.write();
+        config.set_plugin_setting(&state.plugin_name, key, value);
+        config.mark_dirty();
+    }
+    
+    // Blocking GET with a timeout; bodies over HTTP_MAX_RESPONSE_BYTES are an error
+    fn fetch_url(url: &str) -> Result<Vec<u8>> {
+        let response = ureq::AgentBuilder::new()
+            .timeout(HTTP_TIMEOUT)
+            .build()
+            .get(url)
+            .call()?;
+        
+        let mut body = Vec::new();
+        response
+            .into_reader()
+            .take(HTTP_MAX_RESPONSE_BYTES + 1)
+            .read_to_end(&mut body)?;
+        if body.len() as u64 > HTTP_MAX_RESPONSE_BYTES {
+            anyhow::bail!("response larger than {} bytes", HTTP_MAX_RESPONSE_BYTES);
+        }
+        
+        Ok(body)
+    }
+    
+    // Gate for capability-bearing host functions; logs the denial so the user can see why
+    fn check_permission(state: &PluginState, permission: Permission, function: &str) -> bool {
+        if state.permissions.contains(&permission) {
+            return true;
+        }
+        
+        state.console.write().log_error(&format!(
+            "Plugin '{}' denied {}: {:?} permission not declared",
+            state.plugin_name, function, permission
+        ));
+        false
+    }
+    
+    // Pack a time of day into a single u32: (hour << 16) | (minute << 8) | second
+    fn pack_time(time: &impl Timelike) -> u32 {
+        (time.hour() << 16) | (time.minute() << 8) | time.second()
+    }
+    
     fn add_host_functions(linker: &mut Linker<PluginState>) -> Result<()> {
         // get_system_time() -> returns packed u32 with hours, minutes, seconds
         linker.func_wrap(
             "env",
             "get_system_time",
             |_caller: Caller<'_, PluginState>| -> u32 {
-                let now = Local::now();
-                let hour = now.hour();
-                let minute = now.minute();
-                let second = now.second();
-                
-                // Pack into single u32: (hour << 16) | (minute << 8) | second
-                ((hour as u32) << 16) | ((minute as u32) << 8) | (second as u32)
+                Self::pack_time(&Local::now())
+            },
+        )?;
+        
+        // get_time_with_offset(offset_minutes) -> packed u32 like get_system_time, but for
+        // UTC shifted by offset_minutes (e.g. -300 for UTC-5) instead of the host's timezone
+        linker.func_wrap(
+            "env",
+            "get_time_with_offset",
+            |_caller: Caller<'_, PluginState>, offset_minutes: i32| -> u32 {
+                Self::pack_time(&(Utc::now() + chrono::Duration::minutes(offset_minutes as i64)))
             },
         )?;
         
@@ -123,6 +442,108 @@ impl WasmPlugin {
             },
         )?;
         
+        // get_monotonic_millis() -> milliseconds since app start. Unlike the wall-clock
+        // functions this never jumps on DST or clock changes, so use it for cooldowns.
+        linker.func_wrap(
+            "env",
+            "get_monotonic_millis",
+            |_caller: Caller<'_, PluginState>| -> u64 {
+                APP_START.get_or_init(Instant::now).elapsed().as_millis() as u64
+            },
+        )?;
+        
+        // get_app_uptime_seconds() -> whole seconds since the app started. Survives plugin
+        // restarts, so it works as a session clock.
+        linker.func_wrap(
+            "env",
+            "get_app_uptime_seconds",
+            |_caller: Caller<'_, PluginState>| -> u64 {
+                APP_START.get_or_init(Instant::now).elapsed().as_secs()
+            },
+        )?;
+        
+        // ui_set_value(id_ptr, id_len, value)
+        // Sets the live value of a UI element such as a ProgressBar in the plugin's tab
+        linker.func_wrap(
+            "env",
+            "ui_set_value",
+            |mut caller: Caller<'_, PluginState>, id_ptr: i32, id_len: i32, value: f32| {
+                let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
+                    Some(mem) => mem,
+                    None => return,
+                };
+                
+                let data = memory.data(&caller);
+                let id = match Self::plugin_str(caller.data(), data, id_ptr, id_len, "UI element id") {
+                    Some(id) => id,
+                    None => return,
+                };
+                
+                caller.data().ui_values.lock().insert(id, value);
+            },
+        )?;
+        
+        // get_plugin_uptime_seconds() -> whole seconds since this plugin was last started,
+        // 0 while it's stopped
+        linker.func_wrap(
+            "env",
+            "get_plugin_uptime_seconds",
+            |caller: Caller<'_, PluginState>| -> u64 {
+                caller.data().started_at.map_or(0, |started| started.elapsed().as_secs())
+            },
+        )?;
+        
+        // set_timer(interval_ms, timer_id) -> 1 on success, 0 if interval_ms is 0
+        // Calls plugin_on_timer(timer_id) every interval_ms while the plugin runs; setting
+        // an existing id replaces it. All timers are cleared when the plugin stops.
+        linker.func_wrap(
+            "env",
+            "set_timer",
+            |mut caller: Caller<'_, PluginState>, interval_ms: u32, timer_id: i32| -> i32 {
+                if interval_ms == 0 {
+                    return 0;
+                }
+                
+                let interval = Duration::from_millis(interval_ms as u64);
+                caller.data_mut().timers.insert(timer_id, PluginTimer {
+                    interval,
+                    next_fire: Instant::now() + interval,
+                });
+                1
+            },
+        )?;
+        
+        // clear_timer(timer_id)
+        linker.func_wrap(
+            "env",
+            "clear_timer",
+            |mut caller: Caller<'_, PluginState>, timer_id: i32| {
+                caller.data_mut().timers.remove(&timer_id);
+            },
+        )?;
+        
+        // get_random() -> random u32. Not cryptographically secure, only meant for
+        // things like varying chatbox text or jittering timings
+        linker.func_wrap(
+            "env",
+            "get_random",
+            |_caller: Caller<'_, PluginState>| -> u32 {
+                rand::random()
+            },
+        )?;
+        
+        // get_random_range(min, max) -> random i32 in min..=max (same caveat as get_random)
+        linker.func_wrap(
+            "env",
+            "get_random_range",
+            |_caller: Caller<'_, PluginState>, min: i32, max: i32| -> i32 {
+                if min >= max {
+                    return min;
+                }
+                rand::thread_rng().gen_range(min..=max)
+            },
+        )?;
+        
         // load_config(key_ptr, key_len) -> returns value_ptr or 0 if not found
         linker.func_wrap(
             "env",
@@ -134,8 +555,10 @@ impl WasmPlugin {
                 };
                 
                 let data = memory.data(&caller);
-                let key_bytes = &data[key_ptr as usize..(key_ptr + key_len) as usize];
-                let key = String::from_utf8_lossy(key_bytes).to_string();
+                let key = match Self::plugin_str(caller.data(), data, key_ptr, key_len, "config key") {
+                    Some(key) => key,
+                    None => return 0,
+                };
                 
                 let state = caller.data();
                 let config = state.app_config.read();
@@ -173,174 +596,967 @@ impl WasmPlugin {
                 };
                 
                 let data = memory.data(&caller);
-                let key_bytes = &data[key_ptr as usize..(key_ptr + key_len) as usize];
-                let key = String::from_utf8_lossy(key_bytes).to_string();
+                let key = match Self::plugin_str(caller.data(), data, key_ptr, key_len, "config key") {
+                    Some(key) => key,
+                    None => return,
+                };
                 
-                let value_bytes = &data[value_ptr as usize..(value_ptr + value_len) as usize];
-                let value = String::from_utf8_lossy(value_bytes).to_string();
+                let value = match Self::plugin_str(caller.data(), data, value_ptr, value_len, "config value") {
+                    Some(value) => value,
+                    None => return,
+                };
                 
-                let state = caller.data();
-                let mut config = state.app_config.write();
-                config.set_plugin_setting(&state.plugin_name, &key, &value);
+                Self::store_plugin_setting(caller.data(), &key, &value);
+            },
+        )?;
+        
+        // Typed config helpers: the host formats and parses the number so plugins
+        // don't have to. Loads return 0 if the key is missing or not a number.
+        linker.func_wrap(
+            "env",
+            "save_config_i64",
+            |mut caller: Caller<'_, PluginState>, key_ptr: i32, key_len: i32, value: i64| {
+                let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
+                    Some(mem) => mem,
+                    None => return,
+                };
                 
-                // Save to disk
-                if let Err(e) = config.save() {
-                    state.console.write().log_error(&format!("Failed to save config: {}", e));
-                }
+                let data = memory.data(&caller);
+                let key = match Self::plugin_str(caller.data(), data, key_ptr, key_len, "config key") {
+                    Some(key) => key,
+                    None => return,
+                };
+                
+                Self::store_plugin_setting(caller.data(), &key, &value.to_string());
             },
         )?;
         
-        // osc_send_float(address_ptr, address_len, value)
         linker.func_wrap(
             "env",
-            "osc_send_float",
-            |mut caller: Caller<'_, PluginState>, addr_ptr: i32, addr_len: i32, value: f32| -> i32 {
+            "load_config_i64",
+            |mut caller: Caller<'_, PluginState>, key_ptr: i32, key_len: i32| -> i64 {
                 let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
                     Some(mem) => mem,
                     None => return 0,
                 };
                 
                 let data = memory.data(&caller);
-                let addr_bytes = &data[addr_ptr as usize..(addr_ptr + addr_len) as usize];
-                let address = String::from_utf8_lossy(addr_bytes).to_string();
+                let key = match Self::plugin_str(caller.data(), data, key_ptr, key_len, "config key") {
+                    Some(key) => key,
+                    None => return 0,
+                };
                 
                 let state = caller.data();
-                if let Err(e) = state.osc_manager.send_float(&address, value) {
-                    state.console.write().log_error(&format!("OSC send failed: {}", e));
-                    return 0;
-                }
+                state.app_config.read()
+                    .get_plugin_setting(&state.plugin_name, &key)
+                    .and_then(|value| value.trim().parse().ok())
+                    .unwrap_or(0)
+            },
+        )?;
+        
+        linker.func_wrap(
+            "env",
+            "save_config_f32",
+            |mut caller: Caller<'_, PluginState>, key_ptr: i32, key_len: i32, value: f32| {
+                let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
+                    Some(mem) => mem,
+                    None => return,
+                };
                 
-                1
+                let data = memory.data(&caller);
+                let key = match Self::plugin_str(caller.data(), data, key_ptr, key_len, "config key") {
+                    Some(key) => key,
+                    None => return,
+                };
+                
+                Self::store_plugin_setting(caller.data(), &key, &value.to_string());
             },
         )?;
         
-        // osc_send_chatbox(message_ptr, message_len, typing)
         linker.func_wrap(
             "env",
-            "osc_send_chatbox",
-            |mut caller: Caller<'_, PluginState>, msg_ptr: i32, msg_len: i32, typing: i32| -> i32 {
+            "load_config_f32",
+            |mut caller: Caller<'_, PluginState>, key_ptr: i32, key_len: i32| -> f32 {
                 let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
                     Some(mem) => mem,
-                    None => return 0,
+                    None => return 0.0,
                 };
                 
                 let data = memory.data(&caller);
-                let msg_bytes = &data[msg_ptr as usize..(msg_ptr + msg_len) as usize];
-                let message = String::from_utf8_lossy(msg_bytes).to_string();
+                let key = match Self::plugin_str(caller.data(), data, key_ptr, key_len, "config key") {
+                    Some(key) => key,
+                    None => return 0.0,
+                };
                 
                 let state = caller.data();
-                // typing != 0 means open keyboard, typing == 0 means send immediately
-                if let Err(e) = state.osc_manager.send_chatbox(&message, typing != 0) {
-                    state.console.write().log_error(&format!("OSC chatbox send failed: {}", e));
-                    return 0;
+                state.app_config.read()
+                    .get_plugin_setting(&state.plugin_name, &key)
+                    .and_then(|value| value.trim().parse().ok())
+                    .unwrap_or(0.0)
+            },
+        )?;
+        
+        // save_config_now() -> 1 ok, 0 failed
+        // Settings saved with save_config* are written within a second or so; this writes
+        // them immediately, e.g. right after an important change.
+        linker.func_wrap(
+            "env",
+            "save_config_now",
+            |caller: Caller<'_, PluginState>| -> i32 {
+                let state = caller.data();
+                match state.app_config.write().flush() {
+                    Ok(()) => 1,
+                    Err(e) => {
+                        state.console.write().log_error(&format!("Failed to save config: {}", e));
+                        0
+                    }
                 }
-                
-                1
             },
         )?;
         
-        // log_info(msg_ptr, msg_len)
+        // save_blob(key_ptr, key_len, data_ptr, data_len) -> 1 ok, 0 failed
+        // load_blob(key_ptr, key_len, buf_ptr, buf_cap) -> bytes read, or -1 if missing or larger than buf_cap
+        // Like save_config/load_config but for raw bytes, which are stored base64-encoded
+        // in config.toml and come back exactly as saved.
         linker.func_wrap(
             "env",
-            "log_info",
-            |mut caller: Caller<'_, PluginState>, msg_ptr: i32, msg_len: i32| {
+            "save_blob",
+            |mut caller: Caller<'_, PluginState>, key_ptr: i32, key_len: i32, data_ptr: i32, data_len: i32| -> i32 {
                 let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
                     Some(mem) => mem,
-                    None => return,
+                    None => return 0,
                 };
                 
                 let data = memory.data(&caller);
-                let msg_bytes = &data[msg_ptr as usize..(msg_ptr + msg_len) as usize];
-                let message = String::from_utf8_lossy(msg_bytes).to_string();
+                let key = match Self::plugin_str(caller.data(), data, key_ptr, key_len, "config key") {
+                    Some(key) => key,
+                    None => return 0,
+                };
+                let value = match Self::plugin_bytes(caller.data(), data, data_ptr, data_len, "blob") {
+                    Some(value) => value.to_vec(),
+                    None => return 0,
+                };
                 
+                // Written by the update loop shortly after, like save_config
                 let state = caller.data();
-                state.console.write().log_info(&message);
+                let mut config = state.app_config.write();
+                config.set_plugin_blob(&state.plugin_name, &key, &value);
+                config.mark_dirty();
+                1
             },
         )?;
         
-        // log_error(msg_ptr, msg_len)
         linker.func_wrap(
             "env",
-            "log_error",
-            |mut caller: Caller<'_, PluginState>, msg_ptr: i32, msg_len: i32| {
+            "load_blob",
+            |mut caller: Caller<'_, PluginState>, key_ptr: i32, key_len: i32, buf_ptr: i32, buf_cap: i32| -> i32 {
                 let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
                     Some(mem) => mem,
-                    None => return,
+                    None => return -1,
                 };
                 
                 let data = memory.data(&caller);
-                let msg_bytes = &data[msg_ptr as usize..(msg_ptr + msg_len) as usize];
-                let message = String::from_utf8_lossy(msg_bytes).to_string();
+                let key = match Self::plugin_str(caller.data(), data, key_ptr, key_len, "config key") {
+                    Some(key) => key,
+                    None => return -1,
+                };
                 
                 let state = caller.data();
-                state.console.write().log_error(&message);
+                let value = match state.app_config.read().get_plugin_blob(&state.plugin_name, &key) {
+                    Some(value) => value,
+                    None => return -1,
+                };
+                if usize::try_from(buf_cap).map_or(true, |cap| value.len() > cap) {
+                    state.console.write().log_error(&format!("Blob '{}' is {} bytes, larger than the plugin's {} byte buffer", key, value.len(), buf_cap));
+                    return -1;
+                }
+                
+                let (data, state) = memory.data_and_store_mut(&mut caller);
+                match Self::plugin_bytes_mut(state, data, buf_ptr, value.len(), "blob buffer") {
+                    Some(dest) => {
+                        dest.copy_from_slice(&value);
+                        value.len() as i32
+                    }
+                    None => -1,
+                }
             },
         )?;
         
-        Ok(())
-    }
-    
-    pub fn register_osc_boop_listener(&self) -> Result<()> {
-        // Get the configured boop address
-        let config = self.app_config.read();
-        let boop_addr = config
-            .get_plugin_setting(&self.name, "boop_input_address")
-            .unwrap_or_else(|| "/avatar/parameters/OSCBoop".to_string());
-        drop(config);
-        
-        // Register listener with callback to plugin
-        let instance = self.instance.clone();
-        let store = self.store.clone();
-        let console = self.store.lock().data().console.clone();
-        
-        self.store.lock().data().osc_manager.register_listener(
-            boop_addr.clone(),
-            move |_addr, value| {
-                // Call plugin_on_osc_bool when we receive the bool
-                match value {
-                    OscType::Bool(b) => {
-                        let inst = instance.lock();
-                        let mut st = store.lock();
-                        
-                        if let Ok(callback_fn) = inst.get_typed_func::<i32, ()>(&mut *st, "plugin_on_osc_bool") {
-                            let val = if *b { 1 } else { 0 };
-                            if let Err(e) = callback_fn.call(&mut *st, val) {
-                                console.write().log_error(&format!("Failed to call plugin_on_osc_bool: {}", e));
-                            }
-                        }
-                    }
-                    OscType::Float(f) => {
-                        // Treat as bool: non-zero = true
-                        let inst = instance.lock();
-                        let mut st = store.lock();
-                        
-                        if let Ok(callback_fn) = inst.get_typed_func::<i32, ()>(&mut *st, "plugin_on_osc_bool") {
-                            let val = if *f > 0.5 { 1 } else { 0 };
-                            if let Err(e) = callback_fn.call(&mut *st, val) {
-                                console.write().log_error(&format!("Failed to call plugin_on_osc_bool: {}", e));
-                            }
-                        }
+        // get_config_dir(buf_ptr, buf_len) -> path length, or -1 if it can't be created or doesn't fit
+        // The directory is private to the plugin and created on first call. Requires the "fs" permission.
+        linker.func_wrap(
+            "env",
+            "get_config_dir",
+            |mut caller: Caller<'_, PluginState>, buf_ptr: i32, buf_len: i32| -> i32 {
+                let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
+                    Some(mem) => mem,
+                    None => return -1,
+                };
+                
+                let state = caller.data();
+                if !Self::check_permission(state, Permission::Filesystem, "get_config_dir") {
+                    return -1;
+                }
+                if let Err(e) = fs::create_dir_all(&state.data_dir) {
+                    state.console.write().log_error(&format!("Failed to create data dir {}: {}", state.data_dir.display(), e));
+                    return -1;
+                }
+                
+                let path = state.data_dir.to_string_lossy().to_string();
+                let path_bytes = path.as_bytes();
+                if usize::try_from(buf_len).map_or(true, |cap| path_bytes.len() > cap) {
+                    return -1;
+                }
+                
+                let (data, state) = memory.data_and_store_mut(&mut caller);
+                match Self::plugin_bytes_mut(state, data, buf_ptr, path_bytes.len(), "path buffer") {
+                    Some(dest) => {
+                        dest.copy_from_slice(path_bytes);
+                        path_bytes.len() as i32
                     }
-                    _ => {}
+                    None => -1,
                 }
             },
-        );
-        
-        Ok(())
-    }
-    
-    fn read_string_from_memory(memory: &Memory, store: &Store<PluginState>, ptr: i32) -> Result<String> {
-        let data = memory.data(&store);
+        )?;
         
-        // First 4 bytes = length
-        let len_bytes = &data[ptr as usize..ptr as usize + 4];
-        let len = u32::from_le_bytes([len_bytes[0], len_bytes[1], len_bytes[2], len_bytes[3]]) as usize;
+        // fs_write(name_ptr, name_len, data_ptr, data_len) -> 1 on success, 0 on failure,
+        // -1 if the data range is outside plugin memory
+        // fs_read and fs_write require the "fs" permission
+        linker.func_wrap(
+            "env",
+            "fs_write",
+            |mut caller: Caller<'_, PluginState>, name_ptr: i32, name_len: i32, data_ptr: i32, data_len: i32| -> i32 {
+                let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
+                    Some(mem) => mem,
+                    None => return 0,
+                };
+                
+                let data = memory.data(&caller);
+                let name = match Self::plugin_str(caller.data(), data, name_ptr, name_len, "file name") {
+                    Some(name) => name,
+                    None => return 0,
+                };
+                let contents = match Self::plugin_bytes(caller.data(), data, data_ptr, data_len, "file contents") {
+                    Some(contents) => contents.to_vec(),
+                    None => return -1,
+                };
+                
+                let state = caller.data();
+                if !Self::check_permission(state, Permission::Filesystem, "fs_write") {
+                    return 0;
+                }
+                let path = match Self::sandboxed_path(&state.data_dir, &name) {
+                    Some(path) => path,
+                    None => {
+                        state.console.write().log_error(&format!("Plugin '{}' denied write to '{}': not a plain file name", state.plugin_name, name));
+                        return 0;
+                    }
+                };
+                
+                let result = fs::create_dir_all(&state.data_dir).and_then(|_| fs::write(&path, contents));
+                if let Err(e) = result {
+                    state.console.write().log_error(&format!("Failed to write {}: {}", path.display(), e));
+                    return 0;
+                }
+                
+                1
+            },
+        )?;
+        
+        // fs_read(name_ptr, name_len, buf_ptr, buf_cap) -> bytes read, or -1 if missing, denied or larger than buf_cap
+        linker.func_wrap(
+            "env",
+            "fs_read",
+            |mut caller: Caller<'_, PluginState>, name_ptr: i32, name_len: i32, buf_ptr: i32, buf_cap: i32| -> i32 {
+                let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
+                    Some(mem) => mem,
+                    None => return -1,
+                };
+                
+                let data = memory.data(&caller);
+                let name = match Self::plugin_str(caller.data(), data, name_ptr, name_len, "file name") {
+                    Some(name) => name,
+                    None => return -1,
+                };
+                
+                let state = caller.data();
+                if !Self::check_permission(state, Permission::Filesystem, "fs_read") {
+                    return -1;
+                }
+                let path = match Self::sandboxed_path(&state.data_dir, &name) {
+                    Some(path) => path,
+                    None => {
+                        state.console.write().log_error(&format!("Plugin '{}' denied read of '{}': not a plain file name", state.plugin_name, name));
+                        return -1;
+                    }
+                };
+                
+                let contents = match fs::read(&path) {
+                    Ok(contents) => contents,
+                    Err(_) => return -1,
+                };
+                if usize::try_from(buf_cap).map_or(true, |cap| contents.len() > cap) {
+                    state.console.write().log_error(&format!("{} is {} bytes, larger than the plugin's {} byte buffer", path.display(), contents.len(), buf_cap));
+                    return -1;
+                }
+                
+                let (data, state) = memory.data_and_store_mut(&mut caller);
+                match Self::plugin_bytes_mut(state, data, buf_ptr, contents.len(), "file buffer") {
+                    Some(dest) => {
+                        dest.copy_from_slice(&contents);
+                        contents.len() as i32
+                    }
+                    None => -1,
+                }
+            },
+        )?;
+        
+        // http_get(url_ptr, url_len, buf_ptr, buf_cap) -> body length, or -1 on failure
+        // Blocks the caller for at most HTTP_TIMEOUT. Requires the "http" permission.
+        linker.func_wrap(
+            "env",
+            "http_get",
+            |mut caller: Caller<'_, PluginState>, url_ptr: i32, url_len: i32, buf_ptr: i32, buf_cap: i32| -> i32 {
+                let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
+                    Some(mem) => mem,
+                    None => return -1,
+                };
+                
+                let data = memory.data(&caller);
+                let url = match Self::plugin_str(caller.data(), data, url_ptr, url_len, "URL") {
+                    Some(url) => url,
+                    None => return -1,
+                };
+                
+                let state = caller.data();
+                if !Self::check_permission(state, Permission::Http, "http_get") {
+                    return -1;
+                }
+                
+                let body = match Self::fetch_url(&url) {
+                    Ok(body) => body,
+                    Err(e) => {
+                        state.console.write().log_error(&format!("http_get {} failed: {}", url, e));
+                        return -1;
+                    }
+                };
+                if usize::try_from(buf_cap).map_or(true, |cap| body.len() > cap) {
+                    state.console.write().log_error(&format!("http_get {}: {} byte response doesn't fit the plugin's {} byte buffer", url, body.len(), buf_cap));
+                    return -1;
+                }
+                
+                let (data, state) = memory.data_and_store_mut(&mut caller);
+                match Self::plugin_bytes_mut(state, data, buf_ptr, body.len(), "response buffer") {
+                    Some(dest) => {
+                        dest.copy_from_slice(&body);
+                        body.len() as i32
+                    }
+                    None => -1,
+                }
+            },
+        )?;
+        
+        // notify(title_ptr, title_len, body_ptr, body_len) -> 1 if shown, 0 if denied,
+        // rate limited (one per NOTIFY_INTERVAL) or failed. Requires the "notify" permission.
+        linker.func_wrap(
+            "env",
+            "notify",
+            |mut caller: Caller<'_, PluginState>, title_ptr: i32, title_len: i32, body_ptr: i32, body_len: i32| -> i32 {
+                let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
+                    Some(mem) => mem,
+                    None => return 0,
+                };
+                
+                let data = memory.data(&caller);
+                let title = match Self::plugin_str(caller.data(), data, title_ptr, title_len, "notification title") {
+                    Some(title) => title,
+                    None => return 0,
+                };
+                let body = match Self::plugin_str(caller.data(), data, body_ptr, body_len, "notification body") {
+                    Some(body) => body,
+                    None => return 0,
+                };
+                
+                if !Self::check_permission(caller.data(), Permission::Notifications, "notify") {
+                    return 0;
+                }
+                
+                let state = caller.data_mut();
+                if let Some(last) = state.last_notification {
+                    if last.elapsed() < NOTIFY_INTERVAL {
+                        state.console.write().log_error(&format!("Plugin '{}' notification rate limited: {}", state.plugin_name, title));
+                        return 0;
+                    }
+                }
+                state.last_notification = Some(Instant::now());
+                
+                let result = notify_rust::Notification::new()
+                    .appname("Fox OSC")
+                    .summary(&title)
+                    .body(&body)
+                    .show();
+                if let Err(e) = result {
+                    state.console.write().log_error(&format!("Failed to show notification: {}", e));
+                    return 0;
+                }
+                
+                1
+            },
+        )?;
+        
+        // send_plugin_message(target_ptr, target_len, data_ptr, data_len) -> 1 if queued, 0 if the queue
+        // is full or an argument is outside plugin memory
+        // Delivered to the target's plugin_on_message on the next tick, not during this call
+        linker.func_wrap(
+            "env",
+            "send_plugin_message",
+            |mut caller: Caller<'_, PluginState>, target_ptr: i32, target_len: i32, data_ptr: i32, data_len: i32| -> i32 {
+                let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
+                    Some(mem) => mem,
+                    None => return 0,
+                };
+                
+                let data = memory.data(&caller);
+                let target = match Self::plugin_str(caller.data(), data, target_ptr, target_len, "target plugin name") {
+                    Some(target) => target,
+                    None => return 0,
+                };
+                let payload = match Self::plugin_bytes(caller.data(), data, data_ptr, data_len, "message payload") {
+                    Some(payload) => payload.to_vec(),
+                    None => return 0,
+                };
+                
+                let state = caller.data();
+                let mut queue = state.messages.lock();
+                if queue.len() >= MAX_QUEUED_MESSAGES {
+                    state.console.write().log_error(&format!("Plugin message queue full, dropped message from '{}' to '{}'", state.plugin_name, target));
+                    return 0;
+                }
+                
+                queue.push(PluginMessage {
+                    from: state.plugin_name.clone(),
+                    to: target,
+                    data: payload,
+                });
+                1
+            },
+        )?;
+        
+        // osc_begin_batch() / osc_end_batch() -> 1 ok, 0 failed
+        // Sends between the two go out as one OSC bundle: N parameters cost one UDP packet
+        // instead of N. A batch still open when plugin_update returns is flushed then.
+        linker.func_wrap(
+            "env",
+            "osc_begin_batch",
+            |caller: Caller<'_, PluginState>| {
+                caller.data().osc_manager.begin_batch();
+            },
+        )?;
+        
+        linker.func_wrap(
+            "env",
+            "osc_end_batch",
+            |caller: Caller<'_, PluginState>| -> i32 {
+                let state = caller.data();
+                match state.osc_manager.end_batch() {
+                    Ok(_) => 1,
+                    Err(e) => {
+                        state.console.write().log_error(&format!("OSC batch send failed: {}", e));
+                        0
+                    }
+                }
+            },
+        )?;
+        
+        // osc_send_bundle(json_ptr, json_len) -> 1 if the whole bundle was sent, 0 otherwise.
+        // Takes a JSON array of plugin_api::BundleEntry and sends it as one OSC bundle, for
+        // avatar parameters that must change in the same frame. Nothing is sent on error.
+        linker.func_wrap(
+            "env",
+            "osc_send_bundle",
+            |mut caller: Caller<'_, PluginState>, json_ptr: i32, json_len: i32| -> i32 {
+                let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
+                    Some(mem) => mem,
+                    None => return 0,
+                };
+                
+                let data = memory.data(&caller);
+                let state = caller.data();
+                let json_bytes = match Self::plugin_bytes(state, data, json_ptr, json_len, "bundle JSON") {
+                    Some(json_bytes) => json_bytes,
+                    None => return 0,
+                };
+                
+                let entries: Vec<BundleEntry> = match serde_json::from_slice(json_bytes) {
+                    Ok(entries) => entries,
+                    Err(e) => {
+                        state.console.write().log_error(&format!("osc_send_bundle: invalid JSON: {}", e));
+                        return 0;
+                    }
+                };
+                if let Some(entry) = entries.iter().find(|entry| !is_valid_osc_address(&entry.addr)) {
+                    state.console.write().log_error(&format!("osc_send_bundle: invalid OSC address '{}'", entry.addr));
+                    return 0;
+                }
+                
+                let messages = entries
+                    .into_iter()
+                    .map(|entry| {
+                        let arg = match entry.value {
+                            OscValue::Float(f) => OscType::Float(f),
+                            OscValue::Int(i) => OscType::Int(i),
+                            OscValue::Bool(b) => OscType::Bool(b),
+                            OscValue::String(s) => OscType::String(s),
+                        };
+                        (entry.addr, arg)
+                    })
+                    .collect();
+                
+                if let Err(e) = state.osc_manager.send_bundle(messages) {
+                    state.console.write().log_error(&format!("OSC bundle send failed: {}", e));
+                    return 0;
+                }
+                
+                1
+            },
+        )?;
+        
+        // osc_send_raw(bytes_ptr, bytes_len) -> i32
+        // Sends an already-encoded OSC packet as-is, for types the other osc_send_* functions
+        // don't cover. Returns 0 without sending if the bytes don't decode as one OSC packet.
+        linker.func_wrap(
+            "env",
+            "osc_send_raw",
+            |mut caller: Caller<'_, PluginState>, bytes_ptr: i32, bytes_len: i32| -> i32 {
+                let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
+                    Some(mem) => mem,
+                    None => return 0,
+                };
+                
+                let data = memory.data(&caller);
+                let state = caller.data();
+                let bytes = match Self::plugin_bytes(state, data, bytes_ptr, bytes_len, "packet") {
+                    Some(bytes) => bytes,
+                    None => return 0,
+                };
+                
+                if let Err(e) = state.osc_manager.send_raw(bytes) {
+                    state.console.write().log_error(&format!("osc_send_raw: {}", e));
+                    return 0;
+                }
+                
+                1
+            },
+        )?;
+        
+        // osc_send_float(address_ptr, address_len, value)
+        linker.func_wrap(
+            "env",
+            "osc_send_float",
+            |mut caller: Caller<'_, PluginState>, addr_ptr: i32, addr_len: i32, value: f32| -> i32 {
+                let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
+                    Some(mem) => mem,
+                    None => return 0,
+                };
+                
+                let data = memory.data(&caller);
+                let address = match Self::plugin_str(caller.data(), data, addr_ptr, addr_len, "OSC address") {
+                    Some(address) => address,
+                    None => return 0,
+                };
+                
+                let state = caller.data();
+                if let Err(e) = state.osc_manager.send_float(&address, value) {
+                    state.console.write().log_error(&format!("OSC send failed: {}", e));
+                    return 0;
+                }
+                
+                1
+            },
+        )?;
+        
+        // osc_send_normalized(address_ptr, address_len, value, max) -> 1 on success.
+        // Sends `value` out of `max` steps as plugin_api::normalized_value, i.e.
+        // ceil(value * 100 / max) / 100, which Unity's 2-decimal truncation maps back exactly
+        linker.func_wrap(
+            "env",
+            "osc_send_normalized",
+            |mut caller: Caller<'_, PluginState>, addr_ptr: i32, addr_len: i32, value: i32, max: i32| -> i32 {
+                let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
+                    Some(mem) => mem,
+                    None => return 0,
+                };
+                
+                let data = memory.data(&caller);
+                let address = match Self::plugin_str(caller.data(), data, addr_ptr, addr_len, "OSC address") {
+                    Some(address) => address,
+                    None => return 0,
+                };
+                
+                let state = caller.data();
+                if value < 0 || max <= 0 {
+                    state.console.write().log_error(&format!(
+                        "osc_send_normalized: invalid value {} / max {} for {}", value, max, address
+                    ));
+                    return 0;
+                }
+                
+                let normalized = normalized_value(value as u32, max as u32);
+                if let Err(e) = state.osc_manager.send_float(&address, normalized) {
+                    state.console.write().log_error(&format!("OSC send failed: {}", e));
+                    return 0;
+                }
+                
+                1
+            },
+        )?;
+        
+        // osc_get_float(address_ptr, address_len, out_ptr) -> 1 and writes the f32 to out_ptr,
+        // or 0 if nothing numeric was ever received on that address
+        linker.func_wrap(
+            "env",
+            "osc_get_float",
+            |mut caller: Caller<'_, PluginState>, addr_ptr: i32, addr_len: i32, out_ptr: i32| -> i32 {
+                let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
+                    Some(mem) => mem,
+                    None => return 0,
+                };
+                
+                let data = memory.data(&caller);
+                let address = match Self::plugin_str(caller.data(), data, addr_ptr, addr_len, "OSC address") {
+                    Some(address) => address,
+                    None => return 0,
+                };
+                
+                let value = match caller.data().osc_manager.get_float(&address) {
+                    Some(value) => value,
+                    None => return 0,
+                };
+                
+                let (data, state) = memory.data_and_store_mut(&mut caller);
+                match Self::plugin_bytes_mut(state, data, out_ptr, 4, "float output") {
+                    Some(dest) => {
+                        dest.copy_from_slice(&value.to_le_bytes());
+                        1
+                    }
+                    None => 0,
+                }
+            },
+        )?;
+        
+        // osc_send_chatbox(message_ptr, message_len, typing) -> 1 sent, 0 failed, -1 rate limited
+        linker.func_wrap(
+            "env",
+            "osc_send_chatbox",
+            |mut caller: Caller<'_, PluginState>, msg_ptr: i32, msg_len: i32, typing: i32| -> i32 {
+                let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
+                    Some(mem) => mem,
+                    None => return 0,
+                };
+                
+                let data = memory.data(&caller);
+                let message = match Self::plugin_str(caller.data(), data, msg_ptr, msg_len, "message") {
+                    Some(message) => message,
+                    None => return 0,
+                };
+                
+                let state = caller.data();
+                // typing != 0 means open keyboard, typing == 0 means send immediately
+                if let Err(e) = state.osc_manager.send_chatbox(&message, typing != 0) {
+                    if let Some(OscError::ChatboxRateLimited(_)) = e.downcast_ref::<OscError>() {
+                        return -1;
+                    }
+                    state.console.write().log_error(&format!("OSC chatbox send failed: {}", e));
+                    return 0;
+                }
+                
+                1
+            },
+        )?;
+        
+        // log_info(msg_ptr, msg_len)
+        linker.func_wrap(
+            "env",
+            "log_info",
+            |mut caller: Caller<'_, PluginState>, msg_ptr: i32, msg_len: i32| {
+                let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
+                    Some(mem) => mem,
+                    None => return,
+                };
+                
+                let data = memory.data(&caller);
+                let message = match Self::plugin_str(caller.data(), data, msg_ptr, msg_len, "message") {
+                    Some(message) => message,
+                    None => return,
+                };
+                
+                let state = caller.data();
+                state.console.write().log_plugin_info(&state.plugin_name, &message);
+            },
+        )?;
+        
+        // log_error(msg_ptr, msg_len)
+        linker.func_wrap(
+            "env",
+            "log_error",
+            |mut caller: Caller<'_, PluginState>, msg_ptr: i32, msg_len: i32| {
+                let memory = match caller.get_export("memory").and_then(|e| e.into_memory()) {
+                    Some(mem) => mem,
+                    None => return,
+                };
+                
+                let data = memory.data(&caller);
+                let message = match Self::plugin_str(caller.data(), data, msg_ptr, msg_len, "message") {
+                    Some(message) => message,
+                    None => return,
+                };
+                
+                let state = caller.data();
+                state.console.write().log_plugin_error(&state.plugin_name, &message);
+            },
+        )?;
+        
+        Ok(())
+    }
+    
+    pub fn register_osc_boop_listener(&self) -> Result<()> {
+        // Get the configured boop address (saved under the input id, or the older key)
+        let config = self.app_config.read();
+        let boop_addr = config
+            .get_plugin_setting(&self.name, "boop_input")
+            .or_else(|| config.get_plugin_setting(&self.name, "boop_input_address"))
+            .unwrap_or_else(|| "/avatar/parameters/OSCBoop".to_string());
+        drop(config);
+        
+        // Queue values for the next tick rather than calling into the plugin here: this
+        // runs on the receive thread, and a slow plugin would hold up everyone's OSC.
+        // So values reach the plugin up to one tick_interval_ms late, always in the order
+        // they arrived and always before that tick's plugin_update.
+        let running = self.running.clone();
+        let paused = self.paused.clone();
+        let inbox = self.osc_inbox.clone();
+        
+        self.store.lock().data().osc_manager.register_listener(
+            boop_addr.clone(),
+            move |addr, value| {
+                if !*running.read() || *paused.read() {
+                    return;
+                }
+                
+                let mut inbox = inbox.lock();
+                if inbox.values.len() >= MAX_PENDING_OSC {
+                    inbox.values.pop_front();
+                    inbox.dropped += 1;
+                }
+                inbox.values.push_back((addr.to_string(), value.clone()));
+            },
+        );
+        
+        Ok(())
+    }
+    
+    // Hand everything queued by the OSC listener to the plugin, oldest first
+    fn deliver_pending_osc(&self, inst: &Instance, store: &mut Store<PluginState>) {
+        let (values, dropped) = {
+            let mut inbox = self.osc_inbox.lock();
+            (std::mem::take(&mut inbox.values), std::mem::take(&mut inbox.dropped))
+        };
+        
+        if dropped > 0 {
+            store.data().console.write().log_error(&format!(
+                "Plugin '{}' fell behind on OSC input, dropped {} value(s)", self.name, dropped
+            ));
+        }
+        
+        for (address, value) in values {
+            if let Err(e) = Self::dispatch_osc_value(inst, store, &address, &value) {
+                store.data().console.write().log_error(&format!("Failed to deliver OSC value: {}", e));
+            }
+        }
+    }
+    
+    // Hand a received OSC value to the plugin. Plugins exporting the generic
+    //   plugin_on_osc(addr_ptr: i32, addr_len: i32, type_tag: i32, value_bits: u64)
+    // get everything through that, with the address and an OSC_TAG_* from plugin_api.
+    // Otherwise it goes to the typed callback:
+    //   plugin_on_osc_float(value: f32, lossy: i32) for Float and Double (lossy = 1 if the
+    //     double didn't fit in an f32 exactly)
+    //   plugin_on_osc_int(value: i32) for Int and Long (Longs outside i32 are rejected)
+    //   plugin_on_osc_color(rgba: u32) for Color, packed as 0xRRGGBBAA
+    //   plugin_on_osc_bool(value: i32) for Bool
+    // Each typed callback may also be exported with (addr_ptr: i32, addr_len: i32) in
+    // front of its parameters to be told which address the value arrived on.
+    // Plugins that only export plugin_on_osc_bool get numbers as bools (non-zero, > 0.5 for floats).
+    fn dispatch_osc_value(inst: &Instance, store: &mut Store<PluginState>, address: &str, value: &OscType) -> Result<()> {
+        if let Ok(callback_fn) = inst.get_typed_func::<(i32, i32, i32, u64), ()>(&mut *store, "plugin_on_osc") {
+            let (type_tag, value_bits) = match value {
+                OscType::Float(f) => (OSC_TAG_FLOAT, (*f as f64).to_bits()),
+                OscType::Double(d) => (OSC_TAG_FLOAT, d.to_bits()),
+                OscType::Int(i) => (OSC_TAG_INT, *i as i64 as u64),
+                OscType::Long(l) => (OSC_TAG_INT, *l as u64),
+                OscType::Bool(b) => (OSC_TAG_BOOL, *b as u64),
+                OscType::Color(c) => (OSC_TAG_COLOR, u32::from_be_bytes([c.red, c.green, c.blue, c.alpha]) as u64),
+                _ => return Ok(()),
+            };
+            
+            let (addr_ptr, addr_len) = Self::write_osc_address(inst, store, address)?;
+            callback_fn.call(&mut *store, (addr_ptr, addr_len, type_tag, value_bits))?;
+            return Ok(());
+        }
+        
+        match value {
+            OscType::Float(f) => Self::call_float_callback(inst, store, address, *f, false),
+            OscType::Double(d) => {
+                let f = *d as f32;
+                Self::call_float_callback(inst, store, address, f, f as f64 != *d)
+            }
+            OscType::Int(i) => Self::call_int_callback(inst, store, address, *i),
+            OscType::Long(l) => match i32::try_from(*l) {
+                Ok(i) => Self::call_int_callback(inst, store, address, i),
+                Err(_) => anyhow::bail!("OSC long {} is out of range for plugin_on_osc_int", l),
+            },
+            OscType::Color(c) => {
+                let rgba = u32::from_be_bytes([c.red, c.green, c.blue, c.alpha]);
+                if let Ok(callback_fn) = inst.get_typed_func::<(i32, i32, u32), ()>(&mut *store, "plugin_on_osc_color") {
+                    let (addr_ptr, addr_len) = Self::write_osc_address(inst, store, address)?;
+                    callback_fn.call(&mut *store, (addr_ptr, addr_len, rgba))?;
+                } else if let Ok(callback_fn) = inst.get_typed_func::<u32, ()>(&mut *store, "plugin_on_osc_color") {
+                    callback_fn.call(&mut *store, rgba)?;
+                }
+                Ok(())
+            }
+            OscType::Bool(b) => Self::call_bool_callback(inst, store, address, *b),
+            _ => Ok(()),
+        }
+    }
+    
+    fn call_float_callback(inst: &Instance, store: &mut Store<PluginState>, address: &str, value: f32, lossy: bool) -> Result<()> {
+        if let Ok(callback_fn) = inst.get_typed_func::<(i32, i32, f32, i32), ()>(&mut *store, "plugin_on_osc_float") {
+            let (addr_ptr, addr_len) = Self::write_osc_address(inst, store, address)?;
+            return callback_fn.call(&mut *store, (addr_ptr, addr_len, value, lossy as i32));
+        }
+        match inst.get_typed_func::<(f32, i32), ()>(&mut *store, "plugin_on_osc_float") {
+            Ok(callback_fn) => Ok(callback_fn.call(&mut *store, (value, lossy as i32))?),
+            Err(_) => Self::call_bool_callback(inst, store, address, value > 0.5),
+        }
+    }
+    
+    fn call_int_callback(inst: &Instance, store: &mut Store<PluginState>, address: &str, value: i32) -> Result<()> {
+        if let Ok(callback_fn) = inst.get_typed_func::<(i32, i32, i32), ()>(&mut *store, "plugin_on_osc_int") {
+            let (addr_ptr, addr_len) = Self::write_osc_address(inst, store, address)?;
+            return callback_fn.call(&mut *store, (addr_ptr, addr_len, value));
+        }
+        match inst.get_typed_func::<i32, ()>(&mut *store, "plugin_on_osc_int") {
+            Ok(callback_fn) => Ok(callback_fn.call(&mut *store, value)?),
+            Err(_) => Self::call_bool_callback(inst, store, address, value != 0),
+        }
+    }
+    
+    fn call_bool_callback(inst: &Instance, store: &mut Store<PluginState>, address: &str, value: bool) -> Result<()> {
+        if let Ok(callback_fn) = inst.get_typed_func::<(i32, i32, i32), ()>(&mut *store, "plugin_on_osc_bool") {
+            let (addr_ptr, addr_len) = Self::write_osc_address(inst, store, address)?;
+            callback_fn.call(&mut *store, (addr_ptr, addr_len, value as i32))?;
+        } else if let Ok(callback_fn) = inst.get_typed_func::<i32, ()>(&mut *store, "plugin_on_osc_bool") {
+            callback_fn.call(&mut *store, value as i32)?;
+        }
+        Ok(())
+    }
+    
+    // Copy `address` into plugin memory for an OSC callback, returning (ptr, len)
+    fn write_osc_address(inst: &Instance, store: &mut Store<PluginState>, address: &str) -> Result<(i32, i32)> {
+        let memory = inst.get_memory(&mut *store, "memory")
+            .context("Plugin missing memory export")?;
+        let data = memory.data_mut(&mut *store);
+        let end = OSC_ADDRESS_WRITE_POS + address.len();
+        if end >= data.len() {
+            anyhow::bail!("OSC address '{}' is too large for plugin memory", address);
+        }
+        data[OSC_ADDRESS_WRITE_POS..end].copy_from_slice(address.as_bytes());
+        Ok((OSC_ADDRESS_WRITE_POS as i32, address.len() as i32))
+    }
+    
+    fn read_string_from_memory(memory: &Memory, store: &Store<PluginState>, ptr: i32) -> Result<String> {
+        let data = memory.data(&store);
+        let start = usize::try_from(ptr).context("Negative string pointer")?;
+        
+        // First 4 bytes = length
+        let len_bytes = data.get(start..start + 4).context("String length outside plugin memory")?;
+        let len = u32::from_le_bytes([len_bytes[0], len_bytes[1], len_bytes[2], len_bytes[3]]) as usize;
         
         // Next len bytes = data
-        let str_bytes = &data[ptr as usize + 4..ptr as usize + 4 + len];
-        let string = String::from_utf8_lossy(str_bytes).to_string();
+        let str_bytes = data.get(start + 4..start + 4 + len).context("String outside plugin memory")?;
+        let string = std::str::from_utf8(str_bytes).context("String is not valid UTF-8")?;
+        
+        Ok(string.to_string())
+    }
+    
+    // A byte range in plugin memory. Ranges outside memory are logged and refused.
+    fn plugin_bytes<'a>(state: &PluginState, data: &'a [u8], ptr: i32, len: i32, what: &str) -> Option<&'a [u8]> {
+        let bytes = match (usize::try_from(ptr), usize::try_from(len)) {
+            (Ok(start), Ok(len)) => start.checked_add(len).and_then(|end| data.get(start..end)),
+            _ => None,
+        };
+        if bytes.is_none() {
+            state.console.write().log_error(&format!(
+                "Plugin '{}' passed a {} at {} (length {}) outside its memory", state.plugin_name, what, ptr, len
+            ));
+        }
+        bytes
+    }
+    
+    // The writable counterpart of plugin_bytes, for host functions that fill a buffer the
+    // plugin passed in. `len` is what the host is about to write, already checked against
+    // the capacity the plugin claimed.
+    fn plugin_bytes_mut<'a>(state: &PluginState, data: &'a mut [u8], ptr: i32, len: usize, what: &str) -> Option<&'a mut [u8]> {
+        let bytes = usize::try_from(ptr).ok()
+            .and_then(|start| start.checked_add(len).and_then(|end| data.get_mut(start..end)));
+        if bytes.is_none() {
+            state.console.write().log_error(&format!(
+                "Plugin '{}' passed a {} at {} (length {}) outside its memory", state.plugin_name, what, ptr, len
+            ));
+        }
+        bytes
+    }
+    
+    // A string argument from plugin memory. Ranges outside memory and invalid UTF-8 are
+    // logged and refused, where from_utf8_lossy would hand on a mangled string, e.g. an
+    // OSC address that then silently never matches.
+    fn plugin_str(state: &PluginState, data: &[u8], ptr: i32, len: i32, what: &str) -> Option<String> {
+        let bytes = Self::plugin_bytes(state, data, ptr, len, what)?;
+        
+        match std::str::from_utf8(bytes) {
+            Ok(string) => Some(string.to_string()),
+            Err(e) => {
+                state.console.write().log_error(&format!(
+                    "Plugin '{}' passed a {} that isn't valid UTF-8: {}", state.plugin_name, what, e
+                ));
+                None
+            }
+        }
+    }
+    
+    fn check_abi_version(instance: &Instance, store: &mut Store<PluginState>) -> Result<Compat> {
+        let get_version = instance.get_typed_func::<(), u32>(&mut *store, "plugin_abi_version")
+            .map_err(|_| anyhow::anyhow!(
+                "Plugin does not export plugin_abi_version; rebuild it against host ABI v{}",
+                HOST_ABI_VERSION
+            ))?;
+        
+        let version = get_version.call(&mut *store, ())
+            .context("Failed to call plugin_abi_version")?;
+        
+        if !(MIN_PLUGIN_ABI_VERSION..=HOST_ABI_VERSION).contains(&version) {
+            anyhow::bail!(
+                "Plugin was built for host ABI v{}, but this host supports v{} to v{}",
+                version, MIN_PLUGIN_ABI_VERSION, HOST_ABI_VERSION
+            );
+        }
         
-        Ok(string)
+        if version < HOST_ABI_VERSION {
+            Ok(Compat::OlderAbi(version))
+        } else {
+            Ok(Compat::Current)
+        }
     }
     
     fn call_get_info(instance: &Instance, store: &mut Store<PluginState>) -> Result<PluginInfo> {
@@ -394,6 +1610,17 @@ impl WasmPlugin {
         let inst = self.instance.lock();
         let mut store = self.store.lock();
         
+        // Store applied settings under their input id before the plugin sees them, so
+        // plugins can simply read them back with load_config. Written to disk like
+        // save_config, by the update loop shortly after.
+        if let Ok(UiEvent::ApplySettings { values }) = serde_json::from_str::<UiEvent>(event_json) {
+            let mut config = self.app_config.write();
+            for (id, value) in &values {
+                config.set_plugin_setting(&self.name, id, value);
+            }
+            config.mark_dirty();
+        }
+        
         // Call plugin_ui_event if it exists
         if let Ok(ui_event_fn) = inst.get_typed_func::<(i32, i32), ()>(&mut *store, "plugin_ui_event") {
             let bytes = event_json.as_bytes();
@@ -415,19 +1642,84 @@ impl WasmPlugin {
         Ok(())
     }
     
+    // Short status text from the optional plugin_status export
+    pub fn status(&self) -> Option<String> {
+        let status_fn = self.status_fn.as_ref()?;
+        let inst = self.instance.lock();
+        let mut store = self.store.lock();
+        
+        let ptr = status_fn.call(&mut *store, ()).ok()?;
+        if ptr == 0 {
+            return None;
+        }
+        
+        let memory = inst.get_memory(&mut *store, "memory")?;
+        Self::read_string_from_memory(&memory, &store, ptr).ok()
+    }
+    
     pub fn info(&self) -> &PluginInfo {
         &self.info
     }
     
+    /// Name this copy is known by: `info().name`, or `"Name (instance)"` when loaded
+    /// from a `file@instance.wasm`. Settings, data and messages are keyed by it.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    
+    pub fn instance_id(&self) -> Option<&str> {
+        self.instance_id.as_deref()
+    }
+    
     pub fn ui_config(&self) -> Option<&UiConfig> {
         self.ui_config.as_ref()
     }
     
+    pub fn compatibility(&self) -> Compat {
+        self.compatibility
+    }
+    
+    /// Handle to the values this plugin pushes with ui_set_value
+    pub fn ui_values(&self) -> UiValues {
+        self.store.lock().data().ui_values.clone()
+    }
+    
+    /// Names of every function the module exports, sorted
+    pub fn exported_functions(&self) -> Vec<String> {
+        let inst = self.instance.lock();
+        let mut store = self.store.lock();
+        let mut names: Vec<String> = inst
+            .exports(&mut *store)
+            .filter_map(|export| {
+                let name = export.name().to_string();
+                export.into_func().map(|_| name)
+            })
+            .collect();
+        names.sort();
+        names
+    }
+    
+    /// Why the last start, stop or update failed, if it did
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+    
+    fn record_result(&mut self, result: &Result<()>) {
+        self.last_error = result.as_ref().err().map(|e| format!("{:#}", e));
+    }
+    
+    /// Start the plugin, or resume it if it's paused
     pub fn start(&mut self) -> Result<()> {
         if *self.running.read() {
-            return Ok(());
+            return self.resume();
         }
         
+        let result = self.call_start();
+        self.record_result(&result);
+        result
+    }
+    
+    fn call_start(&mut self) -> Result<()> {
         let inst = self.instance.lock();
         let mut store = self.store.lock();
         
@@ -438,6 +1730,8 @@ impl WasmPlugin {
             .context("Failed to call plugin_start")?;
         
         *self.running.write() = true;
+        self.last_update = Some(Instant::now());
+        store.data_mut().started_at = self.last_update;
         store.data().console.write().log_info(&format!("Started plugin: {}", self.name));
         
         Ok(())
@@ -448,6 +1742,58 @@ impl WasmPlugin {
             return Ok(());
         }
         
+        let result = self.call_stop();
+        self.record_result(&result);
+        result
+    }
+    
+    /// Stop calling the plugin without stopping it, so it keeps whatever state it has
+    /// in memory. While paused it gets no updates, timers, OSC values or messages;
+    /// `plugin_pause` is called first if the plugin exports it.
+    pub fn pause(&mut self) -> Result<()> {
+        if !*self.running.read() || *self.paused.read() {
+            return Ok(());
+        }
+        
+        let result = self.call_pause_hook("plugin_pause");
+        self.record_result(&result);
+        result?;
+        
+        *self.paused.write() = true;
+        *self.osc_inbox.lock() = OscInbox::default();
+        self.store.lock().data().console.write().log_info(&format!("Paused plugin: {}", self.name));
+        Ok(())
+    }
+    
+    /// Pick up where `pause` left off, calling `plugin_resume` if the plugin exports it.
+    /// The paused time isn't counted in the next update's `dt_ms`.
+    pub fn resume(&mut self) -> Result<()> {
+        if !*self.paused.read() {
+            return Ok(());
+        }
+        
+        let result = self.call_pause_hook("plugin_resume");
+        self.record_result(&result);
+        result?;
+        
+        *self.paused.write() = false;
+        self.last_update = Some(Instant::now());
+        self.store.lock().data().console.write().log_info(&format!("Resumed plugin: {}", self.name));
+        Ok(())
+    }
+    
+    fn call_pause_hook(&self, hook: &str) -> Result<()> {
+        let inst = self.instance.lock();
+        let mut store = self.store.lock();
+        
+        if let Ok(hook_fn) = inst.get_typed_func::<(), ()>(&mut *store, hook) {
+            hook_fn.call(&mut *store, ())
+                .with_context(|| format!("Failed to call {}", hook))?;
+        }
+        Ok(())
+    }
+    
+    fn call_stop(&mut self) -> Result<()> {
         let inst = self.instance.lock();
         let mut store = self.store.lock();
         
@@ -458,89 +1804,307 @@ impl WasmPlugin {
             .context("Failed to call plugin_stop")?;
         
         *self.running.write() = false;
+        *self.paused.write() = false;
+        *self.osc_inbox.lock() = OscInbox::default();
+        
+        // Don't leave what the plugin saved while stopping waiting on the update loop
+        if self.app_config.read().is_dirty() {
+            if let Err(e) = self.app_config.write().flush() {
+                store.data().console.write().log_error(&format!("Failed to save config: {}", e));
+            }
+        }
+        store.data_mut().timers.clear();
+        store.data_mut().started_at = None;
         store.data().console.write().log_info(&format!("Stopped plugin: {}", self.name));
         
         Ok(())
     }
     
     pub fn update(&mut self) -> Result<()> {
-        if !*self.running.read() {
+        if !*self.running.read() || *self.paused.read() {
+            return Ok(());
+        }
+        
+        let result = self.call_update();
+        self.record_result(&result);
+        result
+    }
+    
+    fn call_update(&mut self) -> Result<()> {
+        let inst = self.instance.lock();
+        let mut store = self.store.lock();
+        
+        // Real time since the previous update, since the timer can drift under load
+        let now = Instant::now();
+        let dt_ms = self.last_update
+            .map(|last| now.duration_since(last).as_millis().min(u32::MAX as u128) as u32)
+            .unwrap_or(0);
+        self.last_update = Some(now);
+        
+        self.deliver_pending_osc(&inst, &mut store);
+        
+        // Call plugin_update(dt_ms) if it exists, falling back to the older no-argument form
+        let started = Instant::now();
+        let mut result = Ok(());
+        if let Ok(update_fn) = inst.get_typed_func::<u32, ()>(&mut *store, "plugin_update") {
+            result = update_fn.call(&mut *store, dt_ms);
+        } else if let Ok(update_fn) = inst.get_typed_func::<(), ()>(&mut *store, "plugin_update") {
+            result = update_fn.call(&mut *store, ());
+        }
+        if result.is_ok() {
+            result = Self::fire_due_timers(&inst, &mut store);
+        }
+        self.metrics.record(started.elapsed());
+        
+        // Don't let a forgotten osc_end_batch hold sends back past this tick
+        if let Err(e) = store.data().osc_manager.end_batch() {
+            store.data().console.write().log_error(&format!("OSC batch send failed: {}", e));
+        }
+        result?;
+        
+        Ok(())
+    }
+    
+    // Timers are checked once per update, so they can't fire more precisely than the
+    // tick interval, but they follow the real clock instead of counting ticks
+    fn fire_due_timers(inst: &Instance, store: &mut Store<PluginState>) -> Result<()> {
+        let now = Instant::now();
+        let mut due = Vec::new();
+        for (id, timer) in store.data_mut().timers.iter_mut() {
+            if timer.next_fire <= now {
+                due.push(*id);
+                timer.next_fire += timer.interval;
+                // Skip missed intervals rather than firing a burst to catch up
+                if timer.next_fire <= now {
+                    timer.next_fire = now + timer.interval;
+                }
+            }
+        }
+        
+        if due.is_empty() {
             return Ok(());
         }
         
+        let timer_fn = match inst.get_typed_func::<i32, ()>(&mut *store, "plugin_on_timer") {
+            Ok(timer_fn) => timer_fn,
+            Err(_) => {
+                // Drop the timers so this is reported once, not on every tick
+                store.data_mut().timers.clear();
+                anyhow::bail!("Plugin set a timer but doesn't export plugin_on_timer");
+            }
+        };
+        due.sort_unstable();
+        for id in due {
+            timer_fn.call(&mut *store, id)?;
+        }
+        
+        Ok(())
+    }
+    
+    // Current size of the plugin's linear memory in bytes
+    pub fn memory_size(&self) -> usize {
+        let inst = self.instance.lock();
+        let mut store = self.store.lock();
+        
+        match inst.get_memory(&mut *store, "memory") {
+            Some(memory) => memory.data_size(&*store),
+            None => 0,
+        }
+    }
+    
+    pub fn metrics(&self) -> PluginMetrics {
+        self.metrics
+    }
+    
+    // Call plugin_on_message(from_ptr, from_len, data_ptr, data_len); plugins without
+    // the export silently ignore messages
+    pub fn receive_message(&mut self, from: &str, payload: &[u8]) -> Result<()> {
         let inst = self.instance.lock();
         let mut store = self.store.lock();
         
-        // Call plugin_update if it exists
-        if let Ok(update_fn) = inst.get_typed_func::<(), ()>(&mut *store, "plugin_update") {
-            update_fn.call(&mut *store, ())?;
+        let message_fn = match inst.get_typed_func::<(i32, i32, i32, i32), ()>(&mut *store, "plugin_on_message") {
+            Ok(message_fn) => message_fn,
+            Err(_) => return Ok(()),
+        };
+        
+        let memory = inst.get_memory(&mut *store, "memory")
+            .context("Plugin missing memory export")?;
+        
+        let from_pos = MESSAGE_WRITE_POS;
+        let data_pos = from_pos + from.len();
+        let data = memory.data_mut(&mut *store);
+        if data_pos + payload.len() >= data.len() {
+            anyhow::bail!("Message from '{}' is too large for plugin memory", from);
         }
+        data[from_pos..data_pos].copy_from_slice(from.as_bytes());
+        data[data_pos..data_pos + payload.len()].copy_from_slice(payload);
+        
+        message_fn.call(&mut *store, (from_pos as i32, from.len() as i32, data_pos as i32, payload.len() as i32))?;
         
         Ok(())
     }
     
+    /// Started and not stopped since; a paused plugin is still running
     pub fn is_running(&self) -> bool {
         *self.running.read()
     }
+    
+    pub fn is_paused(&self) -> bool {
+        *self.paused.read()
+    }
+    
+    /// Whether a running plugin reports state it would lose if stopped without
+    /// `plugin_stop`, via the optional `plugin_has_unsaved_state() -> i32` export
+    pub fn has_unsaved_state(&self) -> bool {
+        if !self.is_running() {
+            return false;
+        }
+        
+        let inst = self.instance.lock();
+        let mut store = self.store.lock();
+        let hook_fn = match inst.get_typed_func::<(), i32>(&mut *store, "plugin_has_unsaved_state") {
+            Ok(hook_fn) => hook_fn,
+            Err(_) => return false,
+        };
+        match hook_fn.call(&mut *store, ()) {
+            Ok(unsaved) => unsaved != 0,
+            Err(e) => {
+                store.data().console.write().log_error(&format!("Plugin '{}' plugin_has_unsaved_state failed: {}", self.name, e));
+                false
+            }
+        }
+    }
 }
 
 pub struct WasmPluginLoader {
     plugins_dir: PathBuf,
+    // Extra directories given on the command line; not saved to the config
+    extra_dirs: Vec<PathBuf>,
+    data_dir: PathBuf,
+    // Why plugins_dir couldn't be created, if it couldn't
+    dir_error: Option<String>,
     plugins: Vec<WasmPlugin>,
+    messages: MessageQueue,
 }
 
 impl WasmPluginLoader {
     pub fn new() -> Result<Self> {
-        let plugins_dir = dirs::config_dir()
-            .ok_or_else(|| anyhow::anyhow!("Failed to get config directory"))?
-            .join("fox-osc")
-            .join("plugins");
+        APP_START.get_or_init(Instant::now);
+        
+        let app_dir = app_dir()?;
+        let plugins_dir = app_dir.join("plugins");
         
-        fs::create_dir_all(&plugins_dir)?;
+        // Not fatal: on e.g. a read-only home the app still runs, just without these plugins
+        let dir_error = fs::create_dir_all(&plugins_dir)
+            .err()
+            .map(|e| format!("Could not create plugin directory {}: {}", plugins_dir.display(), e));
         
         Ok(Self {
             plugins_dir,
+            extra_dirs: Vec::new(),
+            data_dir: app_dir.join("data"),
+            dir_error,
             plugins: Vec::new(),
+            messages: Arc::new(Mutex::new(Vec::new())),
         })
     }
     
+    /// Also scan `dir` for plugins on the next `load_all`
+    pub fn add_plugin_dir(&mut self, dir: PathBuf) {
+        self.extra_dirs.push(dir);
+    }
+    
     pub fn load_all(
         &mut self,
         osc_manager: Arc<OscManager>,
         console: Arc<RwLock<ConsoleLog>>,
         app_config: Arc<RwLock<Config>>,
     ) -> Result<()> {
-        console.write().log_info(&format!("Loading plugins from: {}", self.plugins_dir.display()));
-        
-        // Find all .wasm files
-        let entries = fs::read_dir(&self.plugins_dir)?;
+        let mut dirs = Vec::new();
+        match &self.dir_error {
+            Some(error) => console.write().log_error(error),
+            None => dirs.push(self.plugins_dir.clone()),
+        }
+        dirs.extend(app_config.read().extra_plugin_dirs.iter().cloned());
+        dirs.extend(self.extra_dirs.iter().cloned());
         
-        for entry in entries {
-            let entry = entry?;
-            let path = entry.path();
+        // Collect the plugin files first so the load order doesn't depend on read_dir.
+        // Paths are canonicalized so a file reachable from two directories loads once.
+        let mut paths = Vec::new();
+        let mut seen = HashSet::new();
+        for dir in &dirs {
+            console.write().log_info(&format!("Loading plugins from: {}", dir.display()));
             
-            if path.extension().and_then(|s| s.to_str()) == Some("wasm") {
-                match WasmPlugin::new(&path, osc_manager.clone(), console.clone(), app_config.clone()) {
-                    Ok(mut plugin) => {
-                        console.write().log_info(&format!("✔ Loaded: {}", plugin.info().name));
-                        
-                        // Load config from disk
-                        if let Err(e) = plugin.load_config_from_disk() {
-                            console.write().log_error(&format!("Failed to load config for {}: {}", plugin.info().name, e));
-                        }
-                        
-                        // Register OSC listener for Boop Counter
-                        if plugin.info().name == "Boop Counter" {
-                            if let Err(e) = plugin.register_osc_boop_listener() {
-                                console.write().log_error(&format!("Failed to register OSC listener for {}: {}", plugin.info().name, e));
-                            }
-                        }
-                        
-                        self.plugins.push(plugin);
+            let entries = match fs::read_dir(dir) {
+                Ok(entries) => entries,
+                Err(e) => {
+                    console.write().log_error(&format!("Failed to read plugin directory {}: {}", dir.display(), e));
+                    continue;
+                }
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if WasmPlugin::plugin_file_stem(&path).is_none() {
+                    continue;
+                }
+                let canonical = fs::canonicalize(&path).unwrap_or_else(|_| path.clone());
+                if seen.insert(canonical) {
+                    paths.push(path);
+                }
+            }
+        }
+        
+        // Explicitly ordered plugins first, then everything else by file name
+        let plugin_order = app_config.read().plugin_order.clone();
+        paths.sort_by_key(|path| {
+            let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            let position = plugin_order.iter().position(|n| *n == file_name).unwrap_or(plugin_order.len());
+            (position, file_name)
+        });
+        
+        // Read every plugin's info before instantiating any, so dependencies are set up first
+        let mut prepared: Vec<(PathBuf, PreparedPlugin)> = Vec::new();
+        for path in paths {
+            match WasmPlugin::prepare(&path, &self.data_dir, osc_manager.clone(), console.clone(), app_config.clone(), self.messages.clone()) {
+                Ok(plugin) => {
+                    // Plugins share config and data dirs by name, so a second one would clobber the first
+                    if prepared.iter().any(|(_, p)| p.name == plugin.name) {
+                        console.write().log_error(&format!(
+                            "✗ Skipped {}: a plugin named '{}' is already loaded and they would share settings. Rename the file to name@instance.wasm to run another copy",
+                            path.display(),
+                            plugin.name
+                        ));
+                        continue;
                     }
-                    Err(e) => {
-                        console.write().log_error(&format!("âœ— Failed to load {}: {}", path.display(), e));
+                    prepared.push((path, plugin));
+                }
+                Err(e) => {
+                    console.write().log_error(&format!("✗ Failed to load {}: {}", path.display(), e));
+                }
+            }
+        }
+        
+        for (path, plugin) in Self::sort_by_dependencies(prepared, &console) {
+            match WasmPlugin::instantiate(plugin) {
+                Ok(mut plugin) => {
+                    console.write().log_info(&format!("✔ Loaded: {}", plugin.name()));
+                    
+                    // Load config from disk
+                    if let Err(e) = plugin.load_config_from_disk() {
+                        console.write().log_error(&format!("Failed to load config for {}: {}", plugin.name(), e));
+                    }
+                    
+                    // Register OSC listener for Boop Counter
+                    if plugin.info().name == "Boop Counter" {
+                        if let Err(e) = plugin.register_osc_boop_listener() {
+                            console.write().log_error(&format!("Failed to register OSC listener for {}: {}", plugin.name(), e));
+                        }
                     }
+                    
+                    self.plugins.push(plugin);
+                }
+                Err(e) => {
+                    console.write().log_error(&format!("âœ— Failed to load {}: {}", path.display(), e));
                 }
             }
         }
@@ -550,6 +2114,64 @@ impl WasmPluginLoader {
         Ok(())
     }
     
+    // Reorder so each plugin comes after the ones in its `depends_on`, otherwise keeping
+    // the load order. Plugins are instantiated, started and updated in this order. Unknown
+    // dependencies are reported and ignored; plugins caught in a cycle are reported and go last.
+    fn sort_by_dependencies(plugins: Vec<(PathBuf, PreparedPlugin)>, console: &RwLock<ConsoleLog>) -> Vec<(PathBuf, PreparedPlugin)> {
+        // The instance key ("Timer (2)" for timer@2.wasm) or the shared info name satisfies a dependency
+        let deps: Vec<Vec<usize>> = plugins.iter().map(|(_, plugin)| {
+            plugin.info.depends_on.iter().filter_map(|dep| {
+                let found = plugins.iter().position(|(_, other)| other.name == *dep || other.info.name == *dep);
+                if found.is_none() {
+                    console.write().log_error(&format!("Plugin '{}' depends on '{}', which isn't loaded", plugin.name, dep));
+                }
+                found
+            }).collect()
+        }).collect();
+        
+        let mut order = Vec::with_capacity(plugins.len());
+        let mut placed = vec![false; plugins.len()];
+        while let Some(next) = (0..plugins.len()).find(|&i| !placed[i] && deps[i].iter().all(|&dep| placed[dep])) {
+            placed[next] = true;
+            order.push(next);
+        }
+        
+        let unplaced: Vec<usize> = (0..plugins.len()).filter(|&i| !placed[i]).collect();
+        if !unplaced.is_empty() {
+            let names: Vec<&str> = unplaced.iter().map(|&i| plugins[i].1.name.as_str()).collect();
+            console.write().log_error(&format!(
+                "Circular plugin dependencies involving {}; loading them last in file order", names.join(", ")
+            ));
+            order.extend(unplaced);
+        }
+        
+        let mut slots: Vec<Option<(PathBuf, PreparedPlugin)>> = plugins.into_iter().map(Some).collect();
+        order.into_iter().filter_map(|i| slots[i].take()).collect()
+    }
+    
+    // Hand queued plugin messages to their targets. Called from the update loop, so
+    // no plugin store is locked while another plugin's message is delivered.
+    pub fn deliver_messages(&mut self, console: &RwLock<ConsoleLog>) {
+        let messages: Vec<PluginMessage> = self.messages.lock().drain(..).collect();
+        
+        for message in messages {
+            let target = match self.plugins.iter_mut().find(|p| p.name() == message.to) {
+                Some(plugin) => plugin,
+                None => {
+                    console.write().log_error(&format!("Message from '{}' to unknown plugin '{}'", message.from, message.to));
+                    continue;
+                }
+            };
+            if !target.is_running() || target.is_paused() {
+                continue;
+            }
+            
+            if let Err(e) = target.receive_message(&message.from, &message.data) {
+                console.write().log_error(&format!("Failed to deliver message to '{}': {}", message.to, e));
+            }
+        }
+    }
+    
     pub fn plugins(&self) -> &[WasmPlugin] {
         &self.plugins
     }
@@ -561,4 +2183,204 @@ impl WasmPluginLoader {
     pub fn plugins_dir(&self) -> &Path {
         &self.plugins_dir
     }
-}
\ No newline at end of file
+    
+    /// Why the default plugin directory is unusable, if it is
+    pub fn dir_error(&self) -> Option<&str> {
+        self.dir_error.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    // Send-only so tests don't fight over a listen port
+    fn test_state() -> PluginState {
+        let mut osc = Config::default().osc;
+        osc.target_address = "127.0.0.1:9".to_string();
+        osc.enable_receive = false;
+        let console = Arc::new(RwLock::new(ConsoleLog::new()));
+        
+        PluginState {
+            osc_manager: Arc::new(OscManager::new(&osc, console.clone()).unwrap()),
+            console,
+            app_config: Arc::new(RwLock::new(Config::default())),
+            plugin_name: "test".to_string(),
+            data_dir: std::env::temp_dir(),
+            permissions: Vec::new(),
+            timers: HashMap::new(),
+            last_notification: None,
+            messages: MessageQueue::default(),
+            started_at: None,
+            ui_values: UiValues::default(),
+        }
+    }
+    
+    fn logged_error(state: &PluginState) -> bool {
+        state.console.read().get_entries().iter().any(|entry| entry.level() == crate::console::LogLevel::Error)
+    }
+    
+    // Records what the typed OSC callbacks receive in exported globals
+    const OSC_CALLBACKS_WAT: &str = r#"
+        (module
+            (memory (export "memory") 1)
+            (global $float (export "last_float") (mut f32) (f32.const 0))
+            (global $lossy (export "last_lossy") (mut i32) (i32.const -1))
+            (global $int (export "last_int") (mut i32) (i32.const 0))
+            (global $int_calls (export "int_calls") (mut i32) (i32.const 0))
+            (global $color (export "last_color") (mut i32) (i32.const 0))
+            (func (export "plugin_on_osc_float") (param f32 i32)
+                local.get 0
+                global.set $float
+                local.get 1
+                global.set $lossy)
+            (func (export "plugin_on_osc_int") (param i32)
+                local.get 0
+                global.set $int
+                global.get $int_calls
+                i32.const 1
+                i32.add
+                global.set $int_calls)
+            (func (export "plugin_on_osc_color") (param i32)
+                local.get 0
+                global.set $color))
+    "#;
+    
+    fn osc_callback_plugin() -> (Instance, Store<PluginState>) {
+        let engine = Engine::default();
+        let module = Module::new(&engine, OSC_CALLBACKS_WAT).unwrap();
+        let mut store = Store::new(&engine, test_state());
+        let instance = Linker::new(&engine).instantiate(&mut store, &module).unwrap();
+        (instance, store)
+    }
+    
+    fn global(instance: &Instance, store: &mut Store<PluginState>, name: &str) -> Val {
+        instance.get_global(&mut *store, name).unwrap().get(&mut *store)
+    }
+    
+    #[test]
+    fn dispatch_double_as_float() {
+        let (instance, mut store) = osc_callback_plugin();
+        
+        WasmPlugin::dispatch_osc_value(&instance, &mut store, "/d", &OscType::Double(0.5)).unwrap();
+        assert_eq!(global(&instance, &mut store, "last_float").unwrap_f32(), 0.5);
+        assert_eq!(global(&instance, &mut store, "last_lossy").unwrap_i32(), 0);
+        
+        // 0.1 has no exact f32, so the plugin is told precision was lost
+        WasmPlugin::dispatch_osc_value(&instance, &mut store, "/d", &OscType::Double(0.1)).unwrap();
+        assert_eq!(global(&instance, &mut store, "last_float").unwrap_f32(), 0.1f32);
+        assert_eq!(global(&instance, &mut store, "last_lossy").unwrap_i32(), 1);
+    }
+    
+    #[test]
+    fn dispatch_long_in_range_as_int() {
+        let (instance, mut store) = osc_callback_plugin();
+        
+        WasmPlugin::dispatch_osc_value(&instance, &mut store, "/l", &OscType::Long(-123_456)).unwrap();
+        assert_eq!(global(&instance, &mut store, "last_int").unwrap_i32(), -123_456);
+        assert_eq!(global(&instance, &mut store, "int_calls").unwrap_i32(), 1);
+    }
+    
+    #[test]
+    fn dispatch_long_out_of_range_is_rejected() {
+        let (instance, mut store) = osc_callback_plugin();
+        
+        let result = WasmPlugin::dispatch_osc_value(&instance, &mut store, "/l", &OscType::Long(i64::from(i32::MAX) + 1));
+        assert!(result.is_err());
+        assert_eq!(global(&instance, &mut store, "int_calls").unwrap_i32(), 0);
+    }
+    
+    #[test]
+    fn dispatch_color_as_packed_rgba() {
+        let (instance, mut store) = osc_callback_plugin();
+        let color = OscType::Color(rosc::OscColor { red: 0x12, green: 0x34, blue: 0x56, alpha: 0x78 });
+        
+        WasmPlugin::dispatch_osc_value(&instance, &mut store, "/c", &color).unwrap();
+        assert_eq!(global(&instance, &mut store, "last_color").unwrap_i32() as u32, 0x1234_5678);
+    }
+    
+    #[test]
+    fn pack_time_puts_hours_minutes_and_seconds_in_separate_bytes() {
+        let time = chrono::NaiveTime::from_hms_opt(13, 5, 9).unwrap();
+        assert_eq!(WasmPlugin::pack_time(&time), 0x000D_0509);
+    }
+    
+    #[test]
+    fn plugin_bytes_accepts_ranges_inside_memory() {
+        let state = test_state();
+        let data = b"hello world";
+        
+        assert_eq!(WasmPlugin::plugin_bytes(&state, data, 6, 5, "test"), Some(&b"world"[..]));
+        assert_eq!(WasmPlugin::plugin_bytes(&state, data, 11, 0, "test"), Some(&b""[..]));
+        assert!(!logged_error(&state));
+    }
+    
+    #[test]
+    fn plugin_bytes_rejects_negative_ranges() {
+        let state = test_state();
+        let data = b"hello world";
+        
+        assert_eq!(WasmPlugin::plugin_bytes(&state, data, -1, 4, "test"), None);
+        assert_eq!(WasmPlugin::plugin_bytes(&state, data, 0, -4, "test"), None);
+        assert!(logged_error(&state));
+    }
+    
+    #[test]
+    fn plugin_bytes_rejects_ranges_past_the_end() {
+        let state = test_state();
+        let data = b"hello world";
+        
+        assert_eq!(WasmPlugin::plugin_bytes(&state, data, 6, 6, "test"), None);
+        assert_eq!(WasmPlugin::plugin_bytes(&state, data, 12, 0, "test"), None);
+        // ptr + len overflows i32; must not wrap around into a valid range
+        assert_eq!(WasmPlugin::plugin_bytes(&state, data, i32::MAX, i32::MAX, "test"), None);
+        assert!(logged_error(&state));
+    }
+    
+    #[test]
+    fn plugin_bytes_mut_rejects_negative_and_overflowing_pointers() {
+        let state = test_state();
+        let mut data = [0u8; 8];
+        
+        assert!(WasmPlugin::plugin_bytes_mut(&state, &mut data, 4, 4, "test").is_some());
+        assert!(!logged_error(&state));
+        assert!(WasmPlugin::plugin_bytes_mut(&state, &mut data, -1, 4, "test").is_none());
+        assert!(WasmPlugin::plugin_bytes_mut(&state, &mut data, 5, 4, "test").is_none());
+        assert!(WasmPlugin::plugin_bytes_mut(&state, &mut data, i32::MAX, usize::MAX, "test").is_none());
+        assert!(logged_error(&state));
+    }
+    
+    #[test]
+    fn plugin_str_decodes_utf8() {
+        let state = test_state();
+        let data = "/avatar/parameters/Föx".as_bytes();
+        
+        assert_eq!(
+            WasmPlugin::plugin_str(&state, data, 0, data.len() as i32, "test").as_deref(),
+            Some("/avatar/parameters/Föx")
+        );
+        assert!(!logged_error(&state));
+    }
+    
+    #[test]
+    fn plugin_str_rejects_invalid_utf8() {
+        let state = test_state();
+        let data = [b'/', b'a', 0xff, 0xfe];
+        
+        assert_eq!(WasmPlugin::plugin_str(&state, &data, 0, 4, "test"), None);
+        // Cutting a multi-byte character in half is just as invalid
+        let data = "ö".as_bytes();
+        assert_eq!(WasmPlugin::plugin_str(&state, data, 0, 1, "test"), None);
+        assert!(logged_error(&state));
+    }
+    
+    #[test]
+    fn plugin_str_rejects_out_of_bounds_ranges() {
+        let state = test_state();
+        let data = b"hello";
+        
+        assert_eq!(WasmPlugin::plugin_str(&state, data, -5, 2, "test"), None);
+        assert_eq!(WasmPlugin::plugin_str(&state, data, 3, 10, "test"), None);
+        assert!(logged_error(&state));
+    }
+}
@@ -0,0 +1,185 @@
+use anyhow::{Context, Result};
+use parking_lot::{Mutex, RwLock};
+use rosc::OscType;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tungstenite::Message;
+
+use crate::console::{format_osc_value, osc_type_name, ConsoleLog};
+use crate::osc_manager::OscManager;
+use crate::plugin_api::is_valid_osc_address;
+
+// How long a client thread blocks on reads before flushing queued updates
+const CLIENT_POLL_INTERVAL: Duration = Duration::from_millis(20);
+// Updates queued per client before new ones are dropped for that client
+const CLIENT_QUEUE_SIZE: usize = 1024;
+
+type ClientList = Arc<Mutex<Vec<SyncSender<String>>>>;
+
+/// Message a client sends to have it forwarded out over OSC. `type` is one of
+/// "float", "int", "bool" or "string"; without it the JSON value's type decides.
+#[derive(Debug, Deserialize)]
+struct OutgoingMessage {
+    addr: String,
+    #[serde(rename = "type", default)]
+    kind: Option<String>,
+    value: Value,
+}
+
+/// Start a WebSocket server on `bind_address` that mirrors OSC traffic.
+///
+/// Clients get every current value from the Active Addresses store on connect, then
+/// every received message as `{"addr": ..., "type": ..., "value": ...}`. Messages in
+/// the same shape sent by a client are forwarded out through `osc_manager`.
+pub fn start(bind_address: &str, osc_manager: Arc<OscManager>, console: Arc<RwLock<ConsoleLog>>) -> Result<()> {
+    let listener = TcpListener::bind(bind_address)
+        .with_context(|| format!("Failed to bind WebSocket server to {}", bind_address))?;
+    console.write().log_info(&format!("WebSocket server listening on ws://{}", bind_address));
+    
+    let clients: ClientList = Arc::new(Mutex::new(Vec::new()));
+    
+    let clients_clone = clients.clone();
+    osc_manager.add_observer(move |addr, args| {
+        let json = message_json(addr, args);
+        clients_clone.lock().retain(|tx| !matches!(tx.try_send(json.clone()), Err(TrySendError::Disconnected(_))));
+    });
+    
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    console.write().log_error(&format!("WebSocket accept failed: {}", e));
+                    continue;
+                }
+            };
+            
+            let (tx, rx) = mpsc::sync_channel(CLIENT_QUEUE_SIZE);
+            clients.lock().push(tx);
+            
+            let osc_manager = osc_manager.clone();
+            let console = console.clone();
+            thread::spawn(move || {
+                if let Err(e) = serve_client(stream, rx, &osc_manager, &console) {
+                    console.write().log_error(&format!("WebSocket client error: {}", e));
+                }
+            });
+        }
+    });
+    
+    Ok(())
+}
+
+fn serve_client(
+    stream: TcpStream,
+    updates: Receiver<String>,
+    osc_manager: &OscManager,
+    console: &RwLock<ConsoleLog>,
+) -> Result<()> {
+    let peer = stream.peer_addr().map(|a| a.to_string()).unwrap_or_default();
+    let mut socket = tungstenite::accept(stream).map_err(|e| anyhow::anyhow!("handshake failed: {}", e))?;
+    socket.get_ref().set_read_timeout(Some(CLIENT_POLL_INTERVAL))?;
+    console.write().log_info(&format!("WebSocket client connected: {}", peer));
+    
+    // Initial state, so a fresh overlay doesn't have to wait for every parameter to change
+    let snapshot: Vec<String> = console.read().get_active_addresses()
+        .iter()
+        .map(|(addr, entry)| message_json(addr, &entry.args))
+        .collect();
+    for json in snapshot {
+        socket.send(Message::Text(json))?;
+    }
+    
+    loop {
+        while let Ok(json) = updates.try_recv() {
+            socket.send(Message::Text(json))?;
+        }
+        
+        match socket.read() {
+            Ok(Message::Text(text)) => forward_to_osc(&text, osc_manager, console),
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e)) if matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) => {}
+            Err(tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed) => break,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    
+    console.write().log_info(&format!("WebSocket client disconnected: {}", peer));
+    Ok(())
+}
+
+// Clients send JSON in the same shape they receive
+fn forward_to_osc(text: &str, osc_manager: &OscManager, console: &RwLock<ConsoleLog>) {
+    let message: OutgoingMessage = match serde_json::from_str(text) {
+        Ok(message) => message,
+        Err(e) => {
+            console.write().log_error(&format!("Invalid WebSocket message: {}", e));
+            return;
+        }
+    };
+    if !is_valid_osc_address(&message.addr) {
+        console.write().log_error(&format!("Invalid OSC address from WebSocket client: {}", message.addr));
+        return;
+    }
+    
+    let addr = message.addr.as_str();
+    let value = &message.value;
+    let kind = match message.kind.as_deref() {
+        Some(kind) => kind,
+        // No type given, go by the JSON value
+        None => match value {
+            Value::Bool(_) => "bool",
+            Value::String(_) => "string",
+            Value::Number(n) if n.is_i64() => "int",
+            _ => "float",
+        },
+    };
+    
+    let result = match kind {
+        "float" => value.as_f64()
+            .ok_or_else(|| anyhow::anyhow!("'{}' is not a float", value))
+            .and_then(|v| osc_manager.send_float(addr, v as f32)),
+        "int" => value.as_i64()
+            .ok_or_else(|| anyhow::anyhow!("'{}' is not an int", value))
+            .and_then(|v| osc_manager.send_int(addr, v as i32)),
+        "bool" => value.as_bool()
+            .ok_or_else(|| anyhow::anyhow!("'{}' is not a bool", value))
+            .and_then(|v| osc_manager.send_bool(addr, v)),
+        "string" => value.as_str()
+            .ok_or_else(|| anyhow::anyhow!("'{}' is not a string", value))
+            .and_then(|v| osc_manager.send_string(addr, v)),
+        other => Err(anyhow::anyhow!("Unsupported type '{}'", other)),
+    };
+    
+    if let Err(e) = result {
+        console.write().log_error(&format!("WebSocket to OSC send failed: {}", e));
+    }
+}
+
+// `{"addr", "type", "value"}`; multi-argument messages get comma-joined types and an array value
+fn message_json(addr: &str, args: &[OscType]) -> String {
+    let kind = args.iter().map(osc_type_name).collect::<Vec<_>>().join(",");
+    let value = match args {
+        [arg] => arg_json(arg),
+        args => Value::Array(args.iter().map(arg_json).collect()),
+    };
+    json!({ "addr": addr, "type": kind, "value": value }).to_string()
+}
+
+fn arg_json(arg: &OscType) -> Value {
+    match arg {
+        OscType::Int(i) => json!(i),
+        OscType::Long(l) => json!(l),
+        OscType::Float(f) => json!(f),
+        OscType::Double(d) => json!(d),
+        OscType::Bool(b) => json!(b),
+        OscType::String(s) => json!(s),
+        OscType::Nil => Value::Null,
+        other => json!(format_osc_value(other)),
+    }
+}